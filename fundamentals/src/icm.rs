@@ -0,0 +1,353 @@
+//! Tournament equity by the Independent Chip Model (Malmuth–Harville): each
+//! player finishes first with probability proportional to their stack, and
+//! each later place is decided the same way among the players left.
+
+use crate::pot::{Action, Pot, PotError};
+use crate::side_pot::distribute;
+
+/// The prize equity of each stack, in the units of `payouts`.
+///
+/// `payouts[0]` is paid for first place, `payouts[1]` for second, and so on.
+/// Players with no chips finish behind everyone else and share those places
+/// evenly. The work grows with 2^players, which is fine for a final table.
+pub fn icm(stacks: &[u32], payouts: &[f64]) -> Vec<f64> {
+    let alive: Vec<usize> = (0..stacks.len()).filter(|&i| stacks[i] > 0).collect();
+    let places = payouts.len().min(alive.len());
+    let mut equity = vec![0.0; stacks.len()];
+
+    // probability[mask] is the chance that exactly the players in `mask`
+    // (indices into `alive`) take the top `mask.count_ones()` places.
+    let total: u64 = alive.iter().map(|&i| stacks[i] as u64).sum();
+    let mut probability = vec![0.0; 1 << alive.len()];
+    probability[0] = 1.0;
+    for mask in 0..probability.len() {
+        let place = (mask as u32).count_ones() as usize;
+        if place >= places || probability[mask] == 0.0 {
+            continue;
+        }
+        let taken: u64 = (0..alive.len())
+            .filter(|bit| mask & (1 << bit) != 0)
+            .map(|bit| stacks[alive[bit]] as u64)
+            .sum();
+        let left = (total - taken) as f64;
+        for (bit, &player) in alive.iter().enumerate() {
+            if mask & (1 << bit) != 0 {
+                continue;
+            }
+            let p = probability[mask] * stacks[player] as f64 / left;
+            equity[player] += p * payouts[place];
+            probability[mask | (1 << bit)] += p;
+        }
+    }
+
+    let busted: Vec<usize> = (0..stacks.len()).filter(|&i| stacks[i] == 0).collect();
+    if !busted.is_empty() {
+        let share = payouts.iter().skip(alive.len()).sum::<f64>() / busted.len() as f64;
+        for player in busted {
+            equity[player] = share;
+        }
+    }
+    equity
+}
+
+/// What a decision is worth compared with folding, in chips and in prize
+/// equity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decision {
+    pub chip_ev: f64,
+    pub icm_ev: f64,
+}
+
+impl Decision {
+    /// Whether the decision beats folding once ICM is taken into account.
+    pub fn is_profitable(&self) -> bool {
+        self.icm_ev > 0.0
+    }
+
+    /// Whether chip EV and ICM disagree, as they often do on the bubble.
+    pub fn icm_changes_decision(&self) -> bool {
+        (self.chip_ev > 0.0) != (self.icm_ev > 0.0)
+    }
+}
+
+/// Evaluates `hero` calling an all-in from `villain` in `pot`, everyone
+/// else folding.
+///
+/// `stacks` holds each seat's chips before the hand, and `equity` is the
+/// hero's chance of winning the showdown, ties counted as half.
+pub fn evaluate_call(
+    stacks: &[u32],
+    payouts: &[f64],
+    pot: &Pot,
+    hero: usize,
+    villain: usize,
+    equity: f64,
+) -> Result<Decision, PotError> {
+    check_stacks(stacks, pot, hero, villain)?;
+    let mut pot = pot.clone();
+    fold_others(&mut pot, hero, villain)?;
+
+    let mut fold = pot.clone();
+    fold.apply((hero, Action::Fold))?;
+    let fold = settle(stacks, &fold, hero, villain, false);
+
+    let mut call = pot;
+    call_all_in(&mut call, hero, stacks[hero])?;
+    fold_others(&mut call, hero, villain)?;
+    let win = settle(stacks, &call, hero, villain, true);
+    let lose = settle(stacks, &call, hero, villain, false);
+
+    let chip_ev =
+        equity * win[hero] as f64 + (1.0 - equity) * lose[hero] as f64 - fold[hero] as f64;
+    let icm_ev = equity * icm(&win, payouts)[hero] + (1.0 - equity) * icm(&lose, payouts)[hero]
+        - icm(&fold, payouts)[hero];
+    Ok(Decision { chip_ev, icm_ev })
+}
+
+/// Evaluates `hero` moving all-in against `villain` in `pot`, everyone else
+/// folding.
+///
+/// The villain calls with probability `call_frequency`, and the hero then
+/// wins the showdown with probability `equity`.
+pub fn evaluate_shove(
+    stacks: &[u32],
+    payouts: &[f64],
+    pot: &Pot,
+    hero: usize,
+    villain: usize,
+    call_frequency: f64,
+    equity: f64,
+) -> Result<Decision, PotError> {
+    check_stacks(stacks, pot, hero, villain)?;
+    let mut pot = pot.clone();
+    fold_others(&mut pot, hero, villain)?;
+
+    let mut fold = pot.clone();
+    fold.apply((hero, Action::Fold))?;
+    let fold = settle(stacks, &fold, hero, villain, false);
+
+    let mut shove = pot;
+    let all_in = shove.all_in_to(hero, stacks[hero]);
+    if all_in <= shove.current_bet() {
+        return Err(PotError::CannotRaise(hero));
+    }
+    shove.apply((hero, Action::AllIn(all_in)))?;
+    fold_others(&mut shove, hero, villain)?;
+
+    let mut steal = shove.clone();
+    steal.apply((villain, Action::Fold))?;
+    let steal = settle(stacks, &steal, hero, villain, true);
+
+    let mut called = shove;
+    call_all_in(&mut called, villain, stacks[villain])?;
+    fold_others(&mut called, hero, villain)?;
+    let win = settle(stacks, &called, hero, villain, true);
+    let lose = settle(stacks, &called, hero, villain, false);
+
+    let expected = |value: &dyn Fn(&[u32]) -> f64| {
+        (1.0 - call_frequency) * value(&steal)
+            + call_frequency * (equity * value(&win) + (1.0 - equity) * value(&lose))
+            - value(&fold)
+    };
+    Ok(Decision {
+        chip_ev: expected(&|stacks| stacks[hero] as f64),
+        icm_ev: expected(&|stacks| icm(stacks, payouts)[hero]),
+    })
+}
+
+/// Checks that there is one stack per seat, that `hero` and `villain` are
+/// two different seats at the table, and that every seat has at least the
+/// chips it has put in `pot`.
+fn check_stacks(stacks: &[u32], pot: &Pot, hero: usize, villain: usize) -> Result<(), PotError> {
+    if stacks.len() != pot.table_size() {
+        return Err(PotError::StackCount {
+            stacks: stacks.len(),
+            table_size: pot.table_size(),
+        });
+    }
+    for seat in [hero, villain] {
+        if seat >= pot.table_size() {
+            return Err(PotError::NoSuchSeat(seat));
+        }
+    }
+    if hero == villain {
+        return Err(PotError::SameSeat(hero));
+    }
+    for (seat, &stack) in stacks.iter().enumerate() {
+        let posted = pot.posted(seat);
+        if stack < posted {
+            return Err(PotError::StackTooSmall {
+                seat,
+                stack,
+                posted,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Folds everyone but `hero` and `villain` in turn, until one of them is to
+/// act or the betting is over.
+fn fold_others(pot: &mut Pot, hero: usize, villain: usize) -> Result<(), PotError> {
+    while let Some(seat) = pot.next_to_act() {
+        if seat == hero || seat == villain {
+            break;
+        }
+        pot.apply((seat, Action::Fold))?;
+    }
+    Ok(())
+}
+
+/// Calls the current bet, or as much of it as `stack` covers.
+fn call_all_in(pot: &mut Pot, seat: usize, stack: u32) -> Result<(), PotError> {
+    let all_in = pot.all_in_to(seat, stack);
+    if all_in <= pot.current_bet() {
+        pot.apply((seat, Action::AllIn(all_in)))
+    } else {
+        pot.apply((seat, Action::Call))
+    }
+}
+
+/// The stacks after `pot` is awarded, with `hero` holding the better hand
+/// if `hero_wins`.
+fn settle(stacks: &[u32], pot: &Pot, hero: usize, villain: usize, hero_wins: bool) -> Vec<u32> {
+    let mut strengths = vec![None; stacks.len()];
+    strengths[hero] = Some(hero_wins);
+    strengths[villain] = Some(!hero_wins);
+    let winnings = distribute(&pot.side_pots(), &strengths);
+    (0..stacks.len())
+        .map(|seat| stacks[seat] - pot.posted(seat) + winnings.get(seat).copied().unwrap_or(0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pot::AnteStructure;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    /// Sums every finishing order directly.
+    fn brute_force(stacks: &[u32], payouts: &[f64]) -> Vec<f64> {
+        fn walk(stacks: &[u32], payouts: &[f64], left: Vec<usize>, p: f64, equity: &mut [f64]) {
+            let place = stacks.len() - left.len();
+            if place >= payouts.len() || left.is_empty() {
+                return;
+            }
+            let total: u32 = left.iter().map(|&i| stacks[i]).sum();
+            for &player in &left {
+                let q = p * stacks[player] as f64 / total as f64;
+                equity[player] += q * payouts[place];
+                let rest = left.iter().copied().filter(|&i| i != player).collect();
+                walk(stacks, payouts, rest, q, equity);
+            }
+        }
+        let mut equity = vec![0.0; stacks.len()];
+        walk(
+            stacks,
+            payouts,
+            (0..stacks.len()).collect(),
+            1.0,
+            &mut equity,
+        );
+        equity
+    }
+
+    #[test]
+    fn test_icm() {
+        let equity = icm(&[5000, 3000, 2000], &[50.0, 30.0, 20.0]);
+        assert!(close(equity.iter().sum(), 100.0));
+        assert!(close(equity[0], 38.392857142857146));
+
+        let stacks = [1200, 800, 4000, 350, 2600, 900];
+        let payouts = [40.0, 25.0, 15.0, 10.0];
+        let equity = icm(&stacks, &payouts);
+        for (a, b) in equity.iter().zip(brute_force(&stacks, &payouts)) {
+            assert!(close(*a, b));
+        }
+
+        // Equal stacks share the prizes equally.
+        let equity = icm(&[1000; 4], &[60.0, 40.0]);
+        assert!(equity.iter().all(|&e| close(e, 25.0)));
+    }
+
+    #[test]
+    fn test_busted_players_finish_last() {
+        let equity = icm(&[0, 6000, 4000], &[50.0, 30.0, 20.0]);
+        assert!(close(equity[0], 20.0));
+        assert!(close(equity[1] + equity[2], 80.0));
+    }
+
+    #[test]
+    /// Four left, three paid. The chip leader shoves from the small blind
+    /// into my big blind with a medium stack while a short stack waits to
+    /// bust. A coin flip is a clear chip EV call but an ICM fold.
+    fn test_bubble_call() {
+        let stacks = [10000, 4000, 6000, 800];
+        let payouts = [50.0, 30.0, 20.0];
+        let mut pot = Pot::new(200, 400, AnteStructure::None, 4).unwrap();
+        pot.apply((2, Action::Fold)).unwrap();
+        pot.apply((3, Action::Fold)).unwrap();
+        pot.apply((0, Action::AllIn(10000))).unwrap();
+
+        let decision = evaluate_call(&stacks, &payouts, &pot, 1, 0, 0.5).unwrap();
+        assert!(close(decision.chip_ev, 400.0));
+        assert!(!decision.is_profitable());
+        assert!(decision.icm_changes_decision());
+
+        // The big blind cannot have posted more than its stack.
+        let short = [10000, 300, 6000, 800];
+        assert_eq!(
+            evaluate_call(&short, &payouts, &pot, 1, 0, 0.5),
+            Err(PotError::StackTooSmall {
+                seat: 1,
+                stack: 300,
+                posted: 400
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_seats() {
+        let payouts = [50.0, 30.0, 20.0];
+        let pot = Pot::new(200, 400, AnteStructure::None, 4).unwrap();
+        assert_eq!(
+            evaluate_call(&[10000, 4000, 6000], &payouts, &pot, 1, 0, 0.5),
+            Err(PotError::StackCount {
+                stacks: 3,
+                table_size: 4
+            })
+        );
+
+        let stacks = [10000, 4000, 6000, 800];
+        assert_eq!(
+            evaluate_shove(&stacks, &payouts, &pot, 4, 1, 0.5, 0.5),
+            Err(PotError::NoSuchSeat(4))
+        );
+        assert_eq!(
+            evaluate_call(&stacks, &payouts, &pot, 1, 7, 0.5),
+            Err(PotError::NoSuchSeat(7))
+        );
+        assert_eq!(
+            evaluate_shove(&stacks, &payouts, &pot, 2, 2, 0.5, 0.5),
+            Err(PotError::SameSeat(2))
+        );
+    }
+
+    #[test]
+    fn test_shove() {
+        let stacks = [3000, 3000];
+        let payouts = [100.0];
+        let pot = Pot::new(200, 400, AnteStructure::None, 2).unwrap();
+        // Winner takes all, so $EV is just chip EV scaled.
+        let decision = evaluate_shove(&stacks, &payouts, &pot, 0, 1, 0.5, 0.4).unwrap();
+        assert!(close(decision.icm_ev, decision.chip_ev * 100.0 / 6000.0));
+        // Half the time take the 400, half the time flip for 3000 at 40%.
+        assert!(close(
+            decision.chip_ev,
+            0.5 * 400.0 + 0.5 * (0.4 * 3000.0 - 0.6 * 3000.0) + 200.0
+        ));
+    }
+}
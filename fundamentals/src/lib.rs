@@ -1,22 +1,48 @@
+pub mod icm;
+pub mod odds;
+pub mod position;
 pub mod pot;
+pub mod side_pot;
+pub mod stack;
 
 #[cfg(test)]
 mod tests {
-    use crate::pot::Pot;
+    use crate::odds::{bluff_break_even, minimum_defense_frequency, pot_odds};
+    use crate::position::Position;
+    use crate::pot::{Action, AnteStructure, Limit, Pot, PotError, Preset};
+    use crate::stack::{big_blinds, effective_stack, stack_to_pot_ratio, Rounding};
+    use std::collections::VecDeque;
 
     #[test]
     /// Blinds 600-1200, everyone antes 200. You have 45500.
     /// How many blinds do I have?
     fn core_skill_1() {
-        let result = 45500 / 1200;
+        let result = big_blinds(45500, 1200, Rounding::Down);
         assert_eq!(result, 37);
     }
 
+    #[test]
+    /// Blinds 600-1200, everyone antes 200 at a 9-handed table.
+    /// How much is in the pot before any cards are dealt,
+    /// and how much of it does the big blind have to call?
+    fn everyone_antes() {
+        let pot = Pot::new(600, 1200, AnteStructure::PerPlayer(200), 9).unwrap();
+        assert_eq!(pot.size(), 600 + 1200 + 9 * 200);
+        assert_eq!(pot.dead_money(), 1800);
+        assert_eq!(pot.to_call(2), 1200);
+
+        let big_blind_ante = Pot::new(600, 1200, AnteStructure::BigBlind(1200), 9).unwrap();
+        assert_eq!(big_blind_ante.size(), 3000);
+        let button_ante = Pot::new(600, 1200, AnteStructure::Button(1200), 9).unwrap();
+        assert_eq!(button_ante.size(), 3000);
+        assert_eq!(button_ante.to_call(2), 1200);
+    }
+
     #[test]
     /// Blinds 250-500, the big blind antes 500.
     /// How much is in the pot before any cards are dealt?
     fn core_skill_2() {
-        let pot = Pot::new(250, 500, 500);
+        let pot = Pot::new(250, 500, AnteStructure::BigBlind(500), 9).unwrap();
         let result = pot.size();
         assert_eq!(result, 1250);
     }
@@ -27,8 +53,14 @@ mod tests {
     /// and a middle position player re-raises to 3800.
     /// How much is in the pot?
     fn core_skill_3() {
-        let pot = Pot::new(250, 500, 500);
-        let result = pot.size() + 1500 + 3800;
+        let mut pot = Pot::new(250, 500, AnteStructure::BigBlind(500), 9).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Open(1500)),
+            (3, Action::Fold),
+            (4, Action::ReRaise(3800)),
+        ]))
+        .unwrap();
+        let result = pot.size();
         assert_eq!(result, 6550);
     }
 
@@ -36,7 +68,7 @@ mod tests {
     /// Blinds 100-200, I have 14000.
     /// Do I have more or less than a 70BB stack?
     fn core_skill_4() {
-        let result = 14000 / 200;
+        let result = big_blinds(14000, 200, Rounding::Down);
         assert_eq!(result, 70);
     }
 
@@ -47,8 +79,21 @@ mod tests {
     /// Everyone else folds, I call.
     /// How much is in the pot?
     fn core_skill_5() {
-        let pot = Pot::new(50, 100, 0);
-        let result = pot.size() + 250 + 700 + 450;
+        let mut pot = Pot::new(50, 100, AnteStructure::None, 9).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Open(250)),
+            (3, Action::Fold),
+            (4, Action::ReRaise(700)),
+            (5, Action::Fold),
+            (6, Action::Fold),
+            (7, Action::Fold),
+            (8, Action::Fold),
+            (0, Action::Fold),
+            (1, Action::Fold),
+            (2, Action::Call),
+        ]))
+        .unwrap();
+        let result = pot.size();
         assert_eq!(result, 1550);
     }
 
@@ -59,8 +104,22 @@ mod tests {
     /// The blinds fold, the raiser calls.
     /// How much is in the pot?
     fn core_skill_6() {
-        let pot = Pot::new(200, 400, 400);
-        let result = pot.size() + 825 + 2100 + (2100 - 825);
+        let mut pot = Pot::new(200, 400, AnteStructure::BigBlind(400), 9).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Fold),
+            (3, Action::Fold),
+            (4, Action::Open(825)),
+            (5, Action::Fold),
+            (6, Action::Fold),
+            (7, Action::Fold),
+            (8, Action::ReRaise(2100)),
+            (0, Action::Fold),
+            (1, Action::Fold),
+            (4, Action::Call),
+        ]))
+        .unwrap();
+        assert_eq!(pot.position(8), Some(Position::Button));
+        let result = pot.size();
         assert_eq!(result, 5200);
     }
 
@@ -69,7 +128,7 @@ mod tests {
     /// I have 60000.
     /// Do I have more or less than a 70BB stack?
     fn core_skill_7() {
-        let result = 60000 / 400;
+        let result = big_blinds(60000, 400, Rounding::Down);
         assert!(result > 70);
     }
 
@@ -78,7 +137,7 @@ mod tests {
     /// I have 20200, one of the shortest stacks at the table!
     /// Do I have more or less than a 70BB stack?
     fn core_skill_8() {
-        let result = 20200 / 300;
+        let result = big_blinds(20200, 300, Rounding::Down);
         assert!(result < 70);
     }
 
@@ -89,8 +148,21 @@ mod tests {
     /// A late position player calls, and UTG calls.
     /// How much is in the pot?
     fn core_skill_9() {
-        let pot = Pot::new(150, 300, 0);
-        let result = pot.size() + 800 + 2200 + 2200 + (2200 - 800);
+        let mut pot = Pot::new(150, 300, AnteStructure::None, 9).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Open(800)),
+            (3, Action::ReRaise(2200)),
+            (4, Action::Fold),
+            (5, Action::Fold),
+            (6, Action::Fold),
+            (7, Action::Call),
+            (8, Action::Fold),
+            (0, Action::Fold),
+            (1, Action::Fold),
+            (2, Action::Call),
+        ]))
+        .unwrap();
+        let result = pot.size();
         assert_eq!(result, 7050);
     }
 
@@ -99,7 +171,7 @@ mod tests {
     /// I have 64000.
     /// How many BBs do I have?
     fn core_skill_10() {
-        let result = 64000 / 1200;
+        let result = big_blinds(64000, 1200, Rounding::Down);
         assert_eq!(result, 53);
     }
 
@@ -108,7 +180,7 @@ mod tests {
     /// I have 191000.
     /// How many BBs do I have?
     fn core_skill_11() {
-        let result = 191000 / 1600;
+        let result = big_blinds(191000, 1600, Rounding::Down);
         assert_eq!(result, 119);
     }
 
@@ -121,8 +193,290 @@ mod tests {
     /// I call.
     /// How much is in the pot?
     fn core_skill_12() {
-        let pot = Pot::new(800, 1600, 1600);
-        let result = pot.size() + (22600 * 2);
+        let mut pot = Pot::new(800, 1600, AnteStructure::BigBlind(1600), 9).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Fold),
+            (3, Action::Fold),
+            (4, Action::Fold),
+            (5, Action::Fold),
+            (6, Action::Open(3500)),
+            (7, Action::Fold),
+            (8, Action::ReRaise(8500)),
+            (0, Action::Fold),
+            (1, Action::Fold),
+            (6, Action::Raise(22600)),
+            (8, Action::Call),
+        ]))
+        .unwrap();
+        let result = pot.size();
         assert_eq!(result, 49200);
     }
+
+    #[test]
+    fn invalid_action_sequences() {
+        let mut pot = Pot::new(100, 200, AnteStructure::None, 9).unwrap();
+        assert_eq!(
+            pot.update_size(VecDeque::from([(2, Action::Open(300))])),
+            Err(PotError::RaiseTooSmall {
+                seat: 2,
+                amount: 300,
+                minimum: 400
+            })
+        );
+        let limped: VecDeque<_> = (2..9)
+            .chain([0])
+            .map(|seat| (seat, Action::Call))
+            .chain([(1, Action::Call)])
+            .collect();
+        assert_eq!(pot.update_size(limped), Err(PotError::NothingToCall(1)));
+        assert_eq!(
+            pot.update_size(VecDeque::from([(2, Action::Check)])),
+            Err(PotError::CannotCheck(2))
+        );
+        assert_eq!(
+            pot.update_size(VecDeque::from([
+                (2, Action::Open(500)),
+                (3, Action::Open(1500))
+            ])),
+            Err(PotError::AlreadyOpened(3))
+        );
+        // Open to 500 is a raise of 300, so the re-raise must reach 800.
+        assert_eq!(
+            pot.update_size(VecDeque::from([
+                (2, Action::Open(500)),
+                (3, Action::ReRaise(700))
+            ])),
+            Err(PotError::RaiseTooSmall {
+                seat: 3,
+                amount: 700,
+                minimum: 800
+            })
+        );
+        assert_eq!(
+            pot.update_size(VecDeque::from([
+                (2, Action::Fold),
+                (3, Action::Open(500)),
+                (2, Action::Call)
+            ])),
+            Err(PotError::Folded(2))
+        );
+
+        assert_eq!(
+            pot.update_size(VecDeque::from([(9, Action::Call)])),
+            Err(PotError::NoSuchSeat(9))
+        );
+        assert_eq!(
+            pot.update_size(VecDeque::from([(2, Action::Raise(600))])),
+            Err(PotError::NotOpened(2))
+        );
+        assert_eq!(
+            pot.update_size(VecDeque::from([
+                (2, Action::AllIn(1000)),
+                (3, Action::Call),
+                (2, Action::Call)
+            ])),
+            Err(PotError::AllIn(2))
+        );
+        assert_eq!(
+            pot.update_size(VecDeque::from([(2, Action::Open(500)), (4, Action::Call)])),
+            Err(PotError::OutOfTurn {
+                seat: 4,
+                next: Some(3)
+            })
+        );
+        let folded_to_big_blind: VecDeque<_> = (2..9)
+            .chain([0])
+            .map(|seat| (seat, Action::Fold))
+            .chain([(1, Action::Check)])
+            .collect();
+        assert_eq!(
+            pot.update_size(folded_to_big_blind),
+            Err(PotError::OutOfTurn {
+                seat: 1,
+                next: None
+            })
+        );
+
+        assert_eq!(
+            Pot::new(100, 200, AnteStructure::PerPlayer(25), 1).unwrap_err(),
+            PotError::InvalidTableSize(1)
+        );
+        assert!(Pot::new(100, 200, AnteStructure::None, 10).is_err());
+
+        // Failed sequences leave the pot untouched.
+        assert_eq!(pot.size(), 300);
+    }
+
+    #[test]
+    fn all_in_for_less_does_not_reopen_raising() {
+        let mut pot = Pot::new(100, 200, AnteStructure::None, 9).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Open(600)),
+            (3, Action::AllIn(800)),
+            (4, Action::Call),
+        ]))
+        .unwrap();
+        assert_eq!(pot.current_bet(), 800);
+        assert_eq!(pot.min_raise_to(), 1200);
+        assert_eq!(pot.size(), 100 + 200 + 600 + 800 + 800);
+    }
+
+    #[test]
+    /// Blinds 100-200, the big blind antes 200.
+    /// UTG opens for 600, the short stack next to act is all-in for 450,
+    /// the button calls, the blinds fold, and UTG calls.
+    /// How big are the main pot and the side pot?
+    fn short_stack_all_in_side_pot() {
+        let mut pot = Pot::new(100, 200, AnteStructure::BigBlind(200), 9).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Open(600)),
+            (3, Action::AllIn(450)),
+            (4, Action::Fold),
+            (5, Action::Fold),
+            (6, Action::Fold),
+            (7, Action::Fold),
+            (8, Action::Call),
+            (0, Action::Fold),
+            (1, Action::Fold),
+        ]))
+        .unwrap();
+
+        let pots = pot.side_pots();
+        assert_eq!(pots.len(), 2);
+        assert_eq!(pots[0].amount, 200 + 100 + 200 + 450 * 3);
+        assert_eq!(pots[0].eligible, [2, 3, 8].into_iter().collect());
+        assert_eq!(pots[1].amount, 150 * 2);
+        assert_eq!(pots[1].eligible, [2, 8].into_iter().collect());
+        assert_eq!(pots.iter().map(|pot| pot.amount).sum::<u32>(), pot.size());
+    }
+
+    #[test]
+    /// Blinds 100-200, the big blind antes 200.
+    /// UTG opens for 600 and everyone folds to me in the big blind.
+    /// What pot odds am I getting, and how much equity do I need to call?
+    fn core_skill_13() {
+        let mut pot = Pot::new(100, 200, AnteStructure::BigBlind(200), 9).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Open(600)),
+            (3, Action::Fold),
+            (4, Action::Fold),
+            (5, Action::Fold),
+            (6, Action::Fold),
+            (7, Action::Fold),
+            (8, Action::Fold),
+            (0, Action::Fold),
+        ]))
+        .unwrap();
+        assert_eq!(pot.size(), 1100);
+        assert_eq!(pot.to_call(1), 400);
+        assert!((pot.pot_odds(1).unwrap() - 2.75).abs() < 1e-9);
+        assert!((pot.required_equity(1).unwrap() - 400.0 / 1500.0).abs() < 1e-9);
+        assert_eq!(pot.pot_odds(2), None);
+    }
+
+    #[test]
+    /// Blinds 150-300.
+    /// UTG opens for 800, I make it 2200 in the next seat,
+    /// and everyone folds back to UTG.
+    /// How much equity does UTG need to call?
+    fn core_skill_14() {
+        let mut pot = Pot::new(150, 300, AnteStructure::None, 9).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Open(800)),
+            (3, Action::ReRaise(2200)),
+            (4, Action::Fold),
+            (5, Action::Fold),
+            (6, Action::Fold),
+            (7, Action::Fold),
+            (8, Action::Fold),
+            (0, Action::Fold),
+            (1, Action::Fold),
+        ]))
+        .unwrap();
+        assert_eq!(pot.to_call(2), 1400);
+        let result = (pot.required_equity(2).unwrap() * 100.0).round();
+        assert_eq!(result, 29.0);
+    }
+
+    #[test]
+    /// There is 6000 in the pot on the river and I bet 4000 as a bluff.
+    /// How often does my opponent have to fold for the bluff to break even,
+    /// and how often must they defend?
+    fn core_skill_15() {
+        assert!((bluff_break_even(6000, 4000).unwrap() - 0.4).abs() < 1e-9);
+        assert!((minimum_defense_frequency(6000, 4000).unwrap() - 0.6).abs() < 1e-9);
+        // Calling 4000 into 10000.
+        assert!((pot_odds(10000, 4000).unwrap() - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    /// Blinds 50-100, I have 28000 and the middle position player has 9500.
+    /// From early position, I open for 250.
+    /// The middle position player re-raises to 700.
+    /// Everyone else folds, I call.
+    /// What is the effective stack, and what is the SPR on the flop?
+    fn core_skill_16() {
+        assert_eq!(effective_stack(28000, &[9500]), 9500);
+
+        let mut stacks = vec![10000; 9];
+        stacks[2] = 28000;
+        stacks[4] = 9500;
+        let mut pot = Pot::new(50, 100, AnteStructure::None, 9).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Open(250)),
+            (3, Action::Fold),
+            (4, Action::ReRaise(700)),
+            (5, Action::Fold),
+            (6, Action::Fold),
+            (7, Action::Fold),
+            (8, Action::Fold),
+            (0, Action::Fold),
+            (1, Action::Fold),
+            (2, Action::Call),
+        ]))
+        .unwrap();
+        let spr = stack_to_pot_ratio(&pot, &stacks).unwrap();
+        assert_eq!((spr * 10.0).round() / 10.0, 5.7);
+    }
+
+    #[test]
+    /// Blinds 100-200, the big blind antes 200.
+    /// UTG limps, and I am next with 3000.
+    /// What is a standard open, a pot-sized raise, and my legal raises?
+    fn raise_sizing() {
+        let mut pot = Pot::new(100, 200, AnteStructure::BigBlind(200), 9).unwrap();
+        pot.apply((2, Action::Call)).unwrap();
+        assert_eq!(pot.preset_raise_to(Preset::Open), Some(700));
+        assert_eq!(pot.preset_raise_to(Preset::ThreeBetInPosition), None);
+        // Call 200, making the pot 900, then raise 900 more.
+        assert_eq!(pot.pot_raise_to(3), 1100);
+        assert_eq!(pot.legal_raises(3, 3000, Limit::NoLimit), Some(400..=3000));
+        assert_eq!(pot.legal_raises(3, 3000, Limit::PotLimit), Some(400..=1100));
+        assert_eq!(
+            pot.validate_raise(3, 1500, 3000, Limit::PotLimit),
+            Err(PotError::RaiseTooLarge {
+                seat: 3,
+                amount: 1500,
+                maximum: 1100
+            })
+        );
+
+        pot.apply((3, Action::Open(700))).unwrap();
+        assert_eq!(pot.preset_raise_to(Preset::Open), None);
+        assert_eq!(pot.preset_raise_to(Preset::ThreeBetInPosition), Some(2100));
+        assert_eq!(
+            pot.preset_raise_to(Preset::ThreeBetOutOfPosition),
+            Some(2800)
+        );
+        // The open was a raise of 500, so a 3-bet must reach 1200.
+        assert_eq!(pot.min_raise_to(), 1200);
+        assert!(pot.validate_raise(4, 1200, 5000, Limit::NoLimit).is_ok());
+
+        // Too short for a full raise, but can still move all-in.
+        assert_eq!(pot.legal_raises(4, 1000, Limit::NoLimit), Some(1000..=1000));
+        assert_eq!(
+            pot.validate_raise(4, 700, 700, Limit::NoLimit),
+            Err(PotError::CannotRaise(4))
+        );
+    }
 }
@@ -0,0 +1,77 @@
+//! Pot odds and the frequencies that follow from them.
+//!
+//! `pot` is always what is in the pot before the player in question puts
+//! chips in: when calling, it already holds the bet being called. With no
+//! bet to call or bluff with there is no decision, so every function returns
+//! `None` when the bet is zero.
+
+/// The pot odds for calling `to_call` into `pot`, as "x to 1".
+pub fn pot_odds(pot: u32, to_call: u32) -> Option<f64> {
+    if to_call == 0 {
+        return None;
+    }
+    Some(pot as f64 / to_call as f64)
+}
+
+/// The equity a call of `to_call` into `pot` needs to break even.
+pub fn required_equity(pot: u32, to_call: u32) -> Option<f64> {
+    if to_call == 0 {
+        return None;
+    }
+    Some(to_call as f64 / (pot as f64 + to_call as f64))
+}
+
+/// How often a bet of `bet` into `pot` must make everyone fold for a pure
+/// bluff to break even.
+pub fn bluff_break_even(pot: u32, bet: u32) -> Option<f64> {
+    if bet == 0 {
+        return None;
+    }
+    Some(bet as f64 / (pot as f64 + bet as f64))
+}
+
+/// Minimum defense frequency: how often the player facing a bet of `bet`
+/// into `pot` must continue so that any two cards cannot bluff profitably.
+pub fn minimum_defense_frequency(pot: u32, bet: u32) -> Option<f64> {
+    bluff_break_even(pot, bet).map(|frequency| 1.0 - frequency)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Option<f64>, b: f64) -> bool {
+        matches!(a, Some(a) if (a - b).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_half_pot_bet() {
+        // 100 into 100, facing a bet of 50: the pot holds 150 and costs 50.
+        assert!(close(pot_odds(150, 50), 3.0));
+        assert!(close(required_equity(150, 50), 0.25));
+        assert!(close(bluff_break_even(100, 50), 1.0 / 3.0));
+        assert!(close(minimum_defense_frequency(100, 50), 2.0 / 3.0));
+    }
+
+    #[test]
+    fn test_pot_sized_bet() {
+        assert!(close(pot_odds(200, 100), 2.0));
+        assert!(close(required_equity(200, 100), 1.0 / 3.0));
+        assert!(close(bluff_break_even(100, 100), 0.5));
+        assert!(close(minimum_defense_frequency(100, 100), 0.5));
+    }
+
+    #[test]
+    fn test_no_bet() {
+        assert_eq!(pot_odds(150, 0), None);
+        assert_eq!(required_equity(0, 0), None);
+        assert_eq!(bluff_break_even(0, 0), None);
+        assert_eq!(minimum_defense_frequency(100, 0), None);
+    }
+
+    #[test]
+    fn test_large_amounts() {
+        assert!(close(required_equity(u32::MAX, u32::MAX), 0.5));
+        assert!(close(bluff_break_even(u32::MAX, u32::MAX), 0.5));
+    }
+}
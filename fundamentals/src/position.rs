@@ -0,0 +1,193 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A seat's position relative to the button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Position {
+    UnderTheGun,
+    UnderTheGun1,
+    UnderTheGun2,
+    Lojack,
+    Hijack,
+    Cutoff,
+    Button,
+    SmallBlind,
+    BigBlind,
+}
+
+/// Positions that act before the blinds, in order, at a full ring table.
+const BEFORE_BLINDS: [Position; 7] = [
+    Position::UnderTheGun,
+    Position::UnderTheGun1,
+    Position::UnderTheGun2,
+    Position::Lojack,
+    Position::Hijack,
+    Position::Cutoff,
+    Position::Button,
+];
+
+pub const MAX_TABLE_SIZE: usize = BEFORE_BLINDS.len() + 2;
+
+impl Position {
+    /// The positions at a table of `table_size` players, in pre-flop action
+    /// order, or `None` if the table size is not between 2 and 9.
+    ///
+    /// Seats are filled back from the button, except that with four or more
+    /// players before the blinds the first to act is always UTG and the
+    /// seats between UTG and the lojack fill from the front, so 6-max runs
+    /// UTG, HJ, CO, BTN, SB, BB and 8-handed UTG, UTG+1, LJ, HJ, CO, BTN,
+    /// SB, BB. Heads-up, the button posts the small blind and acts first.
+    pub fn table(table_size: usize) -> Option<Vec<Position>> {
+        if !(2..=MAX_TABLE_SIZE).contains(&table_size) {
+            return None;
+        }
+        if table_size == 2 {
+            return Some(vec![Position::SmallBlind, Position::BigBlind]);
+        }
+        let before_blinds = table_size - 2;
+        // The seats counted back from the button: up to LJ, HJ, CO, BTN,
+        // leaving UTG first to act once there are four or more.
+        let late = if before_blinds >= 4 {
+            (before_blinds - 1).min(4)
+        } else {
+            before_blinds
+        };
+        let mut positions = Vec::with_capacity(table_size);
+        positions.extend_from_slice(&BEFORE_BLINDS[..before_blinds - late]);
+        positions.extend_from_slice(&BEFORE_BLINDS[BEFORE_BLINDS.len() - late..]);
+        positions.extend([Position::SmallBlind, Position::BigBlind]);
+        Some(positions)
+    }
+
+    /// The position of `seat` when `button` holds the button. Seats are
+    /// numbered clockwise from 0.
+    pub fn of(seat: usize, button: usize, table_size: usize) -> Option<Position> {
+        if seat >= table_size || button >= table_size {
+            return None;
+        }
+        let positions = Position::table(table_size)?;
+        // Heads-up the button is the small blind; otherwise the small blind
+        // sits one to the left of the button.
+        let small_blind = if table_size == 2 {
+            button
+        } else {
+            (button + 1) % table_size
+        };
+        let offset = (seat + table_size - small_blind) % table_size;
+        // Action order starts two seats after the small blind.
+        Some(positions[(offset + table_size - 2) % table_size])
+    }
+
+    pub fn is_blind(&self) -> bool {
+        matches!(self, Position::SmallBlind | Position::BigBlind)
+    }
+}
+
+/// Seats in pre-flop action order, with the small blind in seat 0, the big
+/// blind in seat 1 and the button in the last seat, as [`crate::pot::Pot`]
+/// numbers them.
+pub fn preflop_order(table_size: usize) -> Vec<usize> {
+    if table_size <= 2 {
+        (0..table_size).collect()
+    } else {
+        (2..table_size).chain(0..2).collect()
+    }
+}
+
+/// The button seat in [`crate::pot::Pot`]'s numbering.
+pub fn button_seat(table_size: usize) -> usize {
+    if table_size <= 2 {
+        0
+    } else {
+        table_size - 1
+    }
+}
+
+impl FromStr for Position {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "utg" => Ok(Position::UnderTheGun),
+            "utg+1" | "utg1" => Ok(Position::UnderTheGun1),
+            "utg+2" | "utg2" => Ok(Position::UnderTheGun2),
+            "lj" => Ok(Position::Lojack),
+            "hj" => Ok(Position::Hijack),
+            "co" => Ok(Position::Cutoff),
+            "btn" | "bu" => Ok(Position::Button),
+            "sb" => Ok(Position::SmallBlind),
+            "bb" => Ok(Position::BigBlind),
+            _ => Err(format!("Invalid position: {}", s)),
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Position::UnderTheGun => write!(f, "UTG"),
+            Position::UnderTheGun1 => write!(f, "UTG+1"),
+            Position::UnderTheGun2 => write!(f, "UTG+2"),
+            Position::Lojack => write!(f, "LJ"),
+            Position::Hijack => write!(f, "HJ"),
+            Position::Cutoff => write!(f, "CO"),
+            Position::Button => write!(f, "BTN"),
+            Position::SmallBlind => write!(f, "SB"),
+            Position::BigBlind => write!(f, "BB"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(table_size: usize) -> Vec<String> {
+        Position::table(table_size)
+            .unwrap()
+            .iter()
+            .map(Position::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_tables() {
+        assert_eq!(
+            names(9),
+            ["UTG", "UTG+1", "UTG+2", "LJ", "HJ", "CO", "BTN", "SB", "BB"]
+        );
+        assert_eq!(
+            names(8),
+            ["UTG", "UTG+1", "LJ", "HJ", "CO", "BTN", "SB", "BB"]
+        );
+        assert_eq!(names(7), ["UTG", "LJ", "HJ", "CO", "BTN", "SB", "BB"]);
+        assert_eq!(names(6), ["UTG", "HJ", "CO", "BTN", "SB", "BB"]);
+        assert_eq!(names(5), ["HJ", "CO", "BTN", "SB", "BB"]);
+        assert_eq!(names(4), ["CO", "BTN", "SB", "BB"]);
+        assert_eq!(names(3), ["BTN", "SB", "BB"]);
+        assert_eq!(names(2), ["SB", "BB"]);
+        assert_eq!(Position::table(10), None);
+    }
+
+    #[test]
+    fn test_position_of_seat() {
+        // Button in seat 3 at a 6-max table.
+        assert_eq!(Position::of(3, 3, 6), Some(Position::Button));
+        assert_eq!(Position::of(4, 3, 6), Some(Position::SmallBlind));
+        assert_eq!(Position::of(5, 3, 6), Some(Position::BigBlind));
+        assert_eq!(Position::of(0, 3, 6), Some(Position::UnderTheGun));
+        assert_eq!(Position::of(2, 3, 6), Some(Position::Cutoff));
+        assert_eq!(Position::of(1, 0, 2), Some(Position::BigBlind));
+        assert_eq!(Position::of(0, 0, 2), Some(Position::SmallBlind));
+
+        // The pot engine's numbering.
+        for table_size in 2..=MAX_TABLE_SIZE {
+            let button = button_seat(table_size);
+            let positions: Vec<Position> = preflop_order(table_size)
+                .into_iter()
+                .map(|seat| Position::of(seat, button, table_size).unwrap())
+                .collect();
+            assert_eq!(Some(positions), Position::table(table_size));
+        }
+    }
+}
@@ -1,33 +1,538 @@
-use std::collections::VecDeque;
+use crate::odds;
+use crate::position::{button_seat, preflop_order, Position, MAX_TABLE_SIZE};
+use crate::side_pot::{side_pots, SidePot};
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
 
-pub(crate) struct Pot {
-    size: u32,
+/// Seat that posts the small blind. The big blind sits next to it, and the
+/// first player to act pre-flop (UTG) sits next to the big blind.
+pub const SMALL_BLIND_SEAT: usize = 0;
+pub const BIG_BLIND_SEAT: usize = 1;
+
+/// Who posts an ante before the cards are dealt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnteStructure {
+    None,
+    /// Every player at the table antes this amount.
+    PerPlayer(u32),
+    /// The big blind antes for the whole table.
+    BigBlind(u32),
+    /// The button antes for the whole table.
+    Button(u32),
+}
+
+impl AnteStructure {
+    /// Total antes posted at a table of `table_size` players.
+    pub fn total(&self, table_size: usize) -> u32 {
+        match *self {
+            AnteStructure::None => 0,
+            AnteStructure::PerPlayer(ante) => ante * table_size as u32,
+            AnteStructure::BigBlind(ante) | AnteStructure::Button(ante) => ante,
+        }
+    }
+
+    /// The ante posted by `seat` at a table of `table_size` players.
+    pub fn paid_by(&self, seat: usize, table_size: usize) -> u32 {
+        let button = button_seat(table_size);
+        match *self {
+            AnteStructure::PerPlayer(ante) if seat < table_size => ante,
+            AnteStructure::BigBlind(ante) if seat == BIG_BLIND_SEAT => ante,
+            AnteStructure::Button(ante) if seat == button => ante,
+            _ => 0,
+        }
+    }
+}
+
+/// The betting structure, which caps how much a player may raise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    NoLimit,
+    PotLimit,
+}
+
+/// Standard raise sizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// 2.5 big blinds, plus one big blind per limper.
+    Open,
+    /// Three times the open when in position.
+    ThreeBetInPosition,
+    /// Four times the open when out of position.
+    ThreeBetOutOfPosition,
+}
+
+/// A pre-flop pot: the antes, what each seat has put in so far, and the bet
+/// every player still in the hand has to match.
+///
+/// Antes are dead money: they are in the pot but do not count towards
+/// calling or raising. Blinds are live and count as part of their poster's
+/// bet.
+#[derive(Debug, Clone)]
+pub struct Pot {
+    big_blind: u32,
+    ante: AnteStructure,
+    dead: u32,
+    contributions: Vec<u32>,
+    current_bet: u32,
+    last_raise: u32,
+    opened: bool,
+    folded: HashSet<usize>,
+    all_in: HashSet<usize>,
+    /// Seats that have acted at least once.
+    acted: HashSet<usize>,
+    /// The seat whose turn it is, or `None` once the betting is over.
+    next: Option<usize>,
 }
 
 impl Pot {
-    #[allow(unused)]
-    pub fn new(small_blind: u32, big_blind: u32, ante: u32) -> Pot {
-        Pot {
-            size: small_blind + big_blind + ante,
+    /// A pot with the blinds and antes posted, or an error if the table
+    /// does not seat between 2 and 9 players.
+    pub fn new(
+        small_blind: u32,
+        big_blind: u32,
+        ante: AnteStructure,
+        table_size: usize,
+    ) -> Result<Pot, PotError> {
+        if !(2..=MAX_TABLE_SIZE).contains(&table_size) {
+            return Err(PotError::InvalidTableSize(table_size));
         }
+        let mut contributions = vec![0; table_size];
+        contributions[SMALL_BLIND_SEAT] = small_blind;
+        contributions[BIG_BLIND_SEAT] = big_blind;
+        let next = preflop_order(table_size).first().copied();
+        Ok(Pot {
+            big_blind,
+            ante,
+            dead: ante.total(table_size),
+            contributions,
+            current_bet: big_blind,
+            last_raise: big_blind,
+            opened: false,
+            folded: HashSet::new(),
+            all_in: HashSet::new(),
+            acted: HashSet::new(),
+            next,
+        })
     }
 
-    #[allow(unused)]
     pub fn size(&self) -> u32 {
-        self.size
+        self.dead + self.live_money()
+    }
+
+    /// Chips in the pot that belong to no player's bet, such as antes.
+    pub fn dead_money(&self) -> u32 {
+        self.dead
+    }
+
+    /// Chips in the pot that count towards the players' bets.
+    pub fn live_money(&self) -> u32 {
+        self.contributions.iter().sum()
+    }
+
+    pub fn table_size(&self) -> usize {
+        self.contributions.len()
+    }
+
+    /// The position of `seat`, or `None` if there is no such seat.
+    pub fn position(&self, seat: usize) -> Option<Position> {
+        Position::of(seat, button_seat(self.table_size()), self.table_size())
+    }
+
+    /// Seats in pre-flop action order, starting with the first to act.
+    pub fn action_order(&self) -> Vec<usize> {
+        preflop_order(self.table_size())
+    }
+
+    pub fn big_blind(&self) -> u32 {
+        self.big_blind
+    }
+
+    /// Everything `seat` has put in the pot so far, antes included.
+    pub fn posted(&self, seat: usize) -> u32 {
+        self.contribution(seat) + self.ante.paid_by(seat, self.table_size())
+    }
+
+    pub fn has_folded(&self, seat: usize) -> bool {
+        self.folded.contains(&seat)
+    }
+
+    pub fn is_all_in(&self, seat: usize) -> bool {
+        self.all_in.contains(&seat)
+    }
+
+    /// The seat whose turn it is, or `None` once everyone still in the
+    /// hand has matched the bet or is all-in.
+    pub fn next_to_act(&self) -> Option<usize> {
+        self.next
+    }
+
+    /// Applies `actions` in order. Nothing is applied if any of them is
+    /// invalid.
+    pub fn update_size(&mut self, mut actions: VecDeque<PlayerAction>) -> Result<(), PotError> {
+        let mut pot = self.clone();
+        while let Some(action) = actions.pop_front() {
+            pot.apply(action)?;
+        }
+        *self = pot;
+        Ok(())
+    }
+
+    /// Applies one action. Seats act in pre-flop order, each in turn until
+    /// everyone still in the hand has matched the bet, and folded and all-in
+    /// seats never act again.
+    pub fn apply(&mut self, (seat, action): PlayerAction) -> Result<(), PotError> {
+        if seat >= self.contributions.len() {
+            return Err(PotError::NoSuchSeat(seat));
+        }
+        if self.folded.contains(&seat) {
+            return Err(PotError::Folded(seat));
+        }
+        if self.all_in.contains(&seat) {
+            return Err(PotError::AllIn(seat));
+        }
+        if self.next != Some(seat) {
+            return Err(PotError::OutOfTurn {
+                seat,
+                next: self.next,
+            });
+        }
+
+        match action {
+            Action::Fold => {
+                self.folded.insert(seat);
+            }
+            Action::Check => {
+                if self.to_call(seat) > 0 {
+                    return Err(PotError::CannotCheck(seat));
+                }
+            }
+            Action::Call => {
+                if self.to_call(seat) == 0 {
+                    return Err(PotError::NothingToCall(seat));
+                }
+                self.contributions[seat] = self.current_bet;
+            }
+            Action::Open(amount) => {
+                if self.opened {
+                    return Err(PotError::AlreadyOpened(seat));
+                }
+                self.raise_to(seat, amount)?;
+            }
+            Action::Raise(amount) | Action::ReRaise(amount) => {
+                if !self.opened {
+                    return Err(PotError::NotOpened(seat));
+                }
+                self.raise_to(seat, amount)?;
+            }
+            Action::AllIn(amount) => {
+                if amount <= self.contributions[seat] {
+                    return Err(PotError::RaiseTooSmall {
+                        seat,
+                        amount,
+                        minimum: self.contributions[seat] + 1,
+                    });
+                }
+                // An all-in short of a full raise does not change the
+                // minimum raise for the players behind.
+                if amount >= self.min_raise_to() {
+                    self.last_raise = amount - self.current_bet;
+                    self.opened = true;
+                }
+                self.current_bet = self.current_bet.max(amount);
+                self.contributions[seat] = amount;
+                self.all_in.insert(seat);
+            }
+        }
+        self.acted.insert(seat);
+        self.next = self.after(seat);
+        Ok(())
     }
 
-    #[allow(unused)]
-    pub fn update_size(&mut self, _actions: VecDeque<PlayerAction>) {
-        self.size += 100
+    /// The first seat after `seat` in action order that still has to act:
+    /// one that is neither folded nor all-in and has yet to act or to match
+    /// the bet. `None` if there is no such seat or only one player is left.
+    fn after(&self, seat: usize) -> Option<usize> {
+        if self.folded.len() + 1 >= self.table_size() {
+            return None;
+        }
+        let order = self.action_order();
+        let start = order.iter().position(|&s| s == seat)?;
+        order
+            .iter()
+            .cycle()
+            .skip(start + 1)
+            .take(order.len())
+            .copied()
+            .find(|&s| {
+                !self.folded.contains(&s)
+                    && !self.all_in.contains(&s)
+                    && (!self.acted.contains(&s) || self.to_call(s) > 0)
+            })
+    }
+
+    fn raise_to(&mut self, seat: usize, amount: u32) -> Result<(), PotError> {
+        let minimum = self.min_raise_to();
+        if amount < minimum {
+            return Err(PotError::RaiseTooSmall {
+                seat,
+                amount,
+                minimum,
+            });
+        }
+        self.last_raise = amount - self.current_bet;
+        self.current_bet = amount;
+        self.contributions[seat] = amount;
+        self.opened = true;
+        Ok(())
+    }
+
+    /// The raise-to amount of a pot-sized raise by `seat`: a call, then a
+    /// raise by the size of the pot after that call.
+    pub fn pot_raise_to(&self, seat: usize) -> u32 {
+        self.current_bet + self.size() + self.to_call(seat)
+    }
+
+    /// The raise-to amount when `seat` goes all-in with `stack`, its chips
+    /// before the blinds and antes.
+    pub fn all_in_to(&self, seat: usize, stack: u32) -> u32 {
+        stack.saturating_sub(self.ante.paid_by(seat, self.table_size()))
+    }
+
+    /// The amounts `seat` may raise to with `stack` behind, or `None` if it
+    /// cannot raise at all. A stack too short for a full raise may still go
+    /// all-in.
+    pub fn legal_raises(
+        &self,
+        seat: usize,
+        stack: u32,
+        limit: Limit,
+    ) -> Option<RangeInclusive<u32>> {
+        let all_in = self.all_in_to(seat, stack);
+        if all_in <= self.current_bet {
+            return None;
+        }
+        let maximum = match limit {
+            Limit::NoLimit => all_in,
+            Limit::PotLimit => all_in.min(self.pot_raise_to(seat)),
+        };
+        let minimum = self.min_raise_to().min(all_in);
+        Some(minimum..=maximum)
+    }
+
+    /// Checks that `seat` may raise to `amount` with `stack` behind.
+    pub fn validate_raise(
+        &self,
+        seat: usize,
+        amount: u32,
+        stack: u32,
+        limit: Limit,
+    ) -> Result<(), PotError> {
+        let legal = self
+            .legal_raises(seat, stack, limit)
+            .ok_or(PotError::CannotRaise(seat))?;
+        if amount < *legal.start() {
+            Err(PotError::RaiseTooSmall {
+                seat,
+                amount,
+                minimum: *legal.start(),
+            })
+        } else if amount > *legal.end() {
+            Err(PotError::RaiseTooLarge {
+                seat,
+                amount,
+                maximum: *legal.end(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The raise-to amount for `preset`, at least a minimum raise. Opens
+    /// need an unopened pot and 3-bets an opened one; otherwise `None`.
+    pub fn preset_raise_to(&self, preset: Preset) -> Option<u32> {
+        let amount = match preset {
+            Preset::Open if !self.opened => {
+                let limpers = self
+                    .contributions
+                    .iter()
+                    .enumerate()
+                    .filter(|&(seat, &chips)| {
+                        seat != SMALL_BLIND_SEAT
+                            && seat != BIG_BLIND_SEAT
+                            && chips == self.big_blind
+                            && !self.folded.contains(&seat)
+                    })
+                    .count() as u32;
+                self.big_blind * 5 / 2 + limpers * self.big_blind
+            }
+            Preset::ThreeBetInPosition if self.opened => self.current_bet * 3,
+            Preset::ThreeBetOutOfPosition if self.opened => self.current_bet * 4,
+            _ => return None,
+        };
+        Some(amount.max(self.min_raise_to()))
+    }
+
+    /// The bet every player still in the hand has to match.
+    pub fn current_bet(&self) -> u32 {
+        self.current_bet
+    }
+
+    /// The smallest amount a player may raise to.
+    pub fn min_raise_to(&self) -> u32 {
+        self.current_bet + self.last_raise
+    }
+
+    /// What `seat` has put in so far, blinds included.
+    pub fn contribution(&self, seat: usize) -> u32 {
+        self.contributions.get(seat).copied().unwrap_or(0)
+    }
+
+    pub fn to_call(&self, seat: usize) -> u32 {
+        self.current_bet - self.contribution(seat)
+    }
+
+    /// The pot odds `seat` is getting on a call, as "x to 1", or `None` if
+    /// it has nothing to call.
+    pub fn pot_odds(&self, seat: usize) -> Option<f64> {
+        odds::pot_odds(self.size(), self.to_call(seat))
+    }
+
+    /// The equity `seat` needs for a call to break even, or `None` if it has
+    /// nothing to call.
+    pub fn required_equity(&self, seat: usize) -> Option<f64> {
+        odds::required_equity(self.size(), self.to_call(seat))
+    }
+
+    /// The main pot and side pots, with the antes in the main pot.
+    pub fn side_pots(&self) -> Vec<SidePot> {
+        side_pots(&self.contributions, &self.folded, self.dead)
     }
 }
 
 pub type PlayerAction = (usize, Action);
 
+/// A pre-flop action. Amounts are "raise to" totals, not increments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
     Open(u32),
     Call,
     Raise(u32),
     ReRaise(u32),
+    Fold,
+    Check,
+    AllIn(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PotError {
+    NothingToCall(usize),
+    CannotCheck(usize),
+    AlreadyOpened(usize),
+    Folded(usize),
+    NoSuchSeat(usize),
+    InvalidTableSize(usize),
+    AllIn(usize),
+    NotOpened(usize),
+    /// `seat` acted while it was `next`'s turn, or after the betting was
+    /// over.
+    OutOfTurn {
+        seat: usize,
+        next: Option<usize>,
+    },
+    CannotRaise(usize),
+    /// A list of stacks doesn't have one stack per seat.
+    StackCount {
+        stacks: usize,
+        table_size: usize,
+    },
+    /// A seat was asked to play against itself.
+    SameSeat(usize),
+    /// `seat` has put more in the pot than its whole stack.
+    StackTooSmall {
+        seat: usize,
+        stack: u32,
+        posted: u32,
+    },
+    RaiseTooSmall {
+        seat: usize,
+        amount: u32,
+        minimum: u32,
+    },
+    RaiseTooLarge {
+        seat: usize,
+        amount: u32,
+        maximum: u32,
+    },
 }
+
+impl Display for PotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PotError::NothingToCall(seat) => write!(f, "Seat {} has nothing to call", seat),
+            PotError::CannotCheck(seat) => write!(f, "Seat {} cannot check facing a bet", seat),
+            PotError::AlreadyOpened(seat) => {
+                write!(f, "Seat {} cannot open an already opened pot", seat)
+            }
+            PotError::Folded(seat) => write!(f, "Seat {} has already folded", seat),
+            PotError::NoSuchSeat(seat) => write!(f, "There is no seat {} at the table", seat),
+            PotError::InvalidTableSize(size) => write!(
+                f,
+                "A table seats 2 to {} players, not {}",
+                MAX_TABLE_SIZE, size
+            ),
+            PotError::AllIn(seat) => write!(f, "Seat {} is already all-in", seat),
+            PotError::NotOpened(seat) => {
+                write!(
+                    f,
+                    "Seat {} cannot raise an unopened pot, it must open",
+                    seat
+                )
+            }
+            PotError::OutOfTurn {
+                seat,
+                next: Some(next),
+            } => write!(f, "Seat {} cannot act before seat {}", seat, next),
+            PotError::OutOfTurn { seat, next: None } => {
+                write!(f, "Seat {} cannot act, the betting is over", seat)
+            }
+            PotError::CannotRaise(seat) => write!(f, "Seat {} cannot raise", seat),
+            PotError::StackCount { stacks, table_size } => write!(
+                f,
+                "Expected {} stacks, one per seat, but got {}",
+                table_size, stacks
+            ),
+            PotError::SameSeat(seat) => write!(f, "Seat {} cannot play against itself", seat),
+            PotError::StackTooSmall {
+                seat,
+                stack,
+                posted,
+            } => write!(
+                f,
+                "Seat {} has put {} in the pot but only has {}",
+                seat, posted, stack
+            ),
+            PotError::RaiseTooSmall {
+                seat,
+                amount,
+                minimum,
+            } => write!(
+                f,
+                "Seat {} cannot raise to {}, the minimum is {}",
+                seat, amount, minimum
+            ),
+            PotError::RaiseTooLarge {
+                seat,
+                amount,
+                maximum,
+            } => write!(
+                f,
+                "Seat {} cannot raise to {}, the maximum is {}",
+                seat, amount, maximum
+            ),
+        }
+    }
+}
+
+impl Error for PotError {}
@@ -0,0 +1,159 @@
+use std::collections::{BTreeSet, HashSet};
+
+/// A pot and the seats that can win it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SidePot {
+    pub amount: u32,
+    pub eligible: BTreeSet<usize>,
+}
+
+/// Splits the chips put in by each seat (indexed by seat) into a main pot and
+/// side pots.
+///
+/// Each pot is capped at the total contribution of one of the players still
+/// in the hand and can only be won by players who matched that cap. Chips
+/// from folded players count towards the pots but do not make them eligible,
+/// and `dead` money such as antes goes to the main pot.
+pub fn side_pots(contributions: &[u32], folded: &HashSet<usize>, dead: u32) -> Vec<SidePot> {
+    let live = |seat: &usize| !folded.contains(seat) && contributions[*seat] > 0;
+    let levels: BTreeSet<u32> = (0..contributions.len())
+        .filter(live)
+        .map(|seat| contributions[seat])
+        .collect();
+
+    let mut pots: Vec<SidePot> = Vec::new();
+    let mut previous = 0;
+    for level in levels {
+        let amount = contributions
+            .iter()
+            .map(|&chips| chips.min(level) - chips.min(previous))
+            .sum();
+        let eligible = (0..contributions.len())
+            .filter(live)
+            .filter(|&seat| contributions[seat] >= level)
+            .collect();
+        pots.push(SidePot { amount, eligible });
+        previous = level;
+    }
+
+    // Folded players may have put in more than anyone still in the hand.
+    let leftover: u32 = contributions
+        .iter()
+        .map(|&chips| chips.saturating_sub(previous))
+        .sum();
+    if let Some(main) = pots.first_mut() {
+        main.amount += dead;
+    }
+    if let Some(last) = pots.last_mut() {
+        last.amount += leftover;
+    }
+    pots
+}
+
+/// Awards each pot to the strongest hand among its eligible seats.
+///
+/// `strengths` holds the showdown strength of each seat (indexed by seat),
+/// with `None` for seats that do not show down. A pot with a single eligible
+/// seat goes to that seat whatever its strength, and one where no eligible
+/// seat shows down is split between all of them. Tied pots are split evenly,
+/// and odd chips go one at a time to the tied seats nearest the small blind,
+/// which acts first after the button.
+///
+/// Returns the chips won by each seat.
+pub fn distribute<T: Ord>(pots: &[SidePot], strengths: &[Option<T>]) -> Vec<u32> {
+    let seats = pots
+        .iter()
+        .flat_map(|pot| pot.eligible.iter().map(|seat| seat + 1))
+        .chain([strengths.len()])
+        .max()
+        .unwrap_or(0);
+    let mut winnings = vec![0; seats];
+
+    for pot in pots {
+        let contenders: Vec<usize> = if pot.eligible.len() == 1 {
+            pot.eligible.iter().copied().collect()
+        } else {
+            let best = pot
+                .eligible
+                .iter()
+                .filter_map(|&seat| strengths.get(seat).and_then(Option::as_ref))
+                .max();
+            pot.eligible
+                .iter()
+                .copied()
+                .filter(|&seat| {
+                    best.is_none() || strengths.get(seat).and_then(Option::as_ref) == best
+                })
+                .collect()
+        };
+        if contenders.is_empty() {
+            continue;
+        }
+
+        let share = pot.amount / contenders.len() as u32;
+        let odd_chips = pot.amount as usize % contenders.len();
+        for (i, &seat) in contenders.iter().enumerate() {
+            winnings[seat] += share + u32::from(i < odd_chips);
+        }
+    }
+    winnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_side_pots() {
+        // Seat 0 is all-in for 100, seats 1 and 2 for 300, seat 3 folded
+        // after putting in 50.
+        let pots = side_pots(&[100, 300, 300, 50], &HashSet::from([3]), 0);
+        assert_eq!(
+            pots,
+            vec![
+                SidePot {
+                    amount: 350,
+                    eligible: BTreeSet::from([0, 1, 2])
+                },
+                SidePot {
+                    amount: 400,
+                    eligible: BTreeSet::from([1, 2])
+                },
+            ]
+        );
+
+        // An uncalled bet forms a pot that only its owner can win.
+        let pots = side_pots(&[100, 500, 300], &HashSet::new(), 30);
+        assert_eq!(pots.len(), 3);
+        assert_eq!(pots[0].amount, 330);
+        assert_eq!(pots[1].amount, 400);
+        assert_eq!(pots[2].amount, 200);
+        assert_eq!(pots[2].eligible, BTreeSet::from([1]));
+    }
+
+    #[test]
+    fn test_distribute() {
+        let pots = side_pots(&[100, 300, 300, 50], &HashSet::from([3]), 0);
+
+        // The short stack wins the main pot, seat 2 wins the side pot.
+        let winnings = distribute(&pots, &[Some(9), Some(1), Some(5), None]);
+        assert_eq!(winnings, vec![350, 0, 400, 0]);
+
+        // Seats 1 and 2 tie and chop both pots.
+        let winnings = distribute(&pots, &[Some(1), Some(5), Some(5), None]);
+        assert_eq!(winnings, vec![0, 175 + 200, 175 + 200, 0]);
+
+        // Nobody eligible for the side pot shows down, so it is chopped
+        // rather than lost.
+        let winnings = distribute(&pots, &[Some(9), None, None, None]);
+        assert_eq!(winnings, vec![350, 200, 200, 0]);
+        assert_eq!(winnings.iter().sum::<u32>(), 750);
+    }
+
+    #[test]
+    fn test_odd_chips_go_to_first_seat_after_button() {
+        let pots = side_pots(&[100, 100, 100], &HashSet::new(), 1);
+        let winnings = distribute(&pots, &[Some(3), Some(1), Some(3)]);
+        assert_eq!(winnings, vec![151, 0, 150]);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::pot::Pot;
+
+/// How a stack is rounded when counted in big blinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    #[default]
+    Down,
+    Nearest,
+    Up,
+}
+
+/// The depth of `stack` in big blinds.
+pub fn big_blinds(stack: u32, big_blind: u32, rounding: Rounding) -> u32 {
+    match rounding {
+        Rounding::Down => stack / big_blind,
+        Rounding::Nearest => (stack + big_blind / 2) / big_blind,
+        Rounding::Up => match stack % big_blind {
+            0 => stack / big_blind,
+            _ => stack / big_blind + 1,
+        },
+    }
+}
+
+/// The most `stack` can win or lose against `opponents`: its own size,
+/// capped by the largest of theirs.
+pub fn effective_stack(stack: u32, opponents: &[u32]) -> u32 {
+    stack.min(opponents.iter().copied().max().unwrap_or(0))
+}
+
+/// The stack-to-pot ratio once the pre-flop betting in `pot` is over.
+///
+/// `stacks` holds each seat's stack before the blinds and antes, indexed by
+/// seat. The effective stack is the smaller of the two largest stacks left
+/// among the players still in the hand. Returns `None` if fewer than two
+/// players are left.
+pub fn stack_to_pot_ratio(pot: &Pot, stacks: &[u32]) -> Option<f64> {
+    let mut remaining: Vec<u32> = stacks
+        .iter()
+        .enumerate()
+        .filter(|&(seat, _)| !pot.has_folded(seat))
+        .map(|(seat, &stack)| stack.saturating_sub(pot.posted(seat)))
+        .collect();
+    if remaining.len() < 2 {
+        return None;
+    }
+    remaining.sort_unstable_by(|a, b| b.cmp(a));
+    Some(remaining[1] as f64 / pot.size() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pot::{Action, AnteStructure};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_big_blinds() {
+        assert_eq!(big_blinds(20200, 300, Rounding::Down), 67);
+        assert_eq!(big_blinds(20200, 300, Rounding::Nearest), 67);
+        assert_eq!(big_blinds(20200, 300, Rounding::Up), 68);
+        assert_eq!(big_blinds(20250, 300, Rounding::Nearest), 68);
+        assert_eq!(big_blinds(21000, 300, Rounding::Up), 70);
+    }
+
+    #[test]
+    fn test_effective_stack() {
+        assert_eq!(effective_stack(5000, &[3000, 12000]), 5000);
+        assert_eq!(effective_stack(5000, &[3000, 4000]), 4000);
+        assert_eq!(effective_stack(5000, &[]), 0);
+    }
+
+    #[test]
+    fn test_stack_to_pot_ratio() {
+        let mut pot = Pot::new(100, 200, AnteStructure::BigBlind(200), 4).unwrap();
+        pot.update_size(VecDeque::from([
+            (2, Action::Open(500)),
+            (3, Action::Fold),
+            (0, Action::Fold),
+        ]))
+        .unwrap();
+        let mut folded = pot.clone();
+
+        pot.apply((1, Action::Call)).unwrap();
+        // The big blind paid the ante too, so 20000 - 700 is left behind.
+        let spr = stack_to_pot_ratio(&pot, &[10000, 20000, 30000, 8000]).unwrap();
+        assert!((spr - 19300.0 / 1300.0).abs() < 1e-9);
+
+        folded.apply((1, Action::Fold)).unwrap();
+        assert_eq!(
+            stack_to_pot_ratio(&folded, &[10000, 20000, 30000, 8000]),
+            None
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Pot-size and big-blind-count questions generated from random tournament
+//! hands and checked against the `fundamentals` pot engine.
+
+use crate::history::Outcome;
+use crate::session::Session;
+use clap::ValueEnum;
+use fundamentals::pot::{Action, AnteStructure, Pot};
+use fundamentals::stack::{big_blinds, Rounding};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const BLIND_LEVELS: [(u32, u32); 11] = [
+    (50, 100),
+    (100, 200),
+    (150, 300),
+    (200, 400),
+    (250, 500),
+    (300, 600),
+    (400, 800),
+    (500, 1000),
+    (600, 1200),
+    (800, 1600),
+    (1000, 2000),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Difficulty {
+    /// Round raise sizes and no antes.
+    Easy,
+    /// Big blind antes and a few more actions.
+    Medium,
+    /// Any ante structure, long action sequences and sizes like 825.
+    Hard,
+}
+
+impl Difficulty {
+    /// The most players who put chips in after the blinds.
+    fn max_actions(&self) -> usize {
+        match self {
+            Difficulty::Easy => 2,
+            Difficulty::Medium => 3,
+            Difficulty::Hard => 5,
+        }
+    }
+
+    /// Raise sizes are multiples of this.
+    fn step(&self, big_blind: u32) -> u32 {
+        match self {
+            Difficulty::Easy => big_blind,
+            Difficulty::Medium => big_blind / 2,
+            Difficulty::Hard => 25,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Question {
+    /// "pot size" or "big blinds".
+    pub category: &'static str,
+    pub prompt: String,
+    pub answer: u32,
+}
+
+/// A random hand: the blinds, antes and the action so far, and a question
+/// about the pot or the hero's stack.
+pub fn generate<R: Rng>(rng: &mut R, difficulty: Difficulty) -> Question {
+    let (small_blind, big_blind) = *BLIND_LEVELS.choose(rng).unwrap();
+    let table_size = match difficulty {
+        Difficulty::Hard => *[6, 9].choose(rng).unwrap(),
+        _ => 9,
+    };
+    let ante = match (difficulty, rng.gen_range(0..4)) {
+        (Difficulty::Easy, _) | (_, 0) => AnteStructure::None,
+        (Difficulty::Hard, 1) => AnteStructure::PerPlayer(big_blind / 8),
+        (Difficulty::Hard, 2) => AnteStructure::Button(big_blind),
+        _ => AnteStructure::BigBlind(big_blind),
+    };
+
+    let mut prompt = format!("Blinds {}-{}", small_blind, big_blind);
+    match ante {
+        AnteStructure::None => {}
+        AnteStructure::PerPlayer(ante) => prompt += &format!(", everyone antes {}", ante),
+        AnteStructure::BigBlind(ante) => prompt += &format!(", the big blind antes {}", ante),
+        AnteStructure::Button(ante) => prompt += &format!(", the button antes {}", ante),
+    }
+    prompt += &format!(", {}-handed.\n", table_size);
+
+    if rng.gen_bool(0.3) {
+        let step = difficulty.step(big_blind);
+        let stack = rng.gen_range(10..=150) * big_blind + rng.gen_range(0..big_blind / step) * step;
+        prompt += &format!(
+            "I have {}. How many big blinds is that, rounded down?",
+            stack
+        );
+        return Question {
+            category: "big blinds",
+            prompt,
+            answer: big_blinds(stack, big_blind, Rounding::Down),
+        };
+    }
+
+    // Tables are always 6- or 9-handed, so the size is valid.
+    let mut pot = Pot::new(small_blind, big_blind, ante, table_size).unwrap();
+    for line in play(rng, &mut pot, difficulty) {
+        prompt += &line;
+        prompt += "\n";
+    }
+    prompt += "How much is in the pot?";
+    Question {
+        category: "pot size",
+        prompt,
+        answer: pot.size(),
+    }
+}
+
+/// Plays random pre-flop action into `pot` and describes it, one line per
+/// player who puts chips in. Everyone else folds.
+fn play<R: Rng>(rng: &mut R, pot: &mut Pot, difficulty: Difficulty) -> Vec<String> {
+    let order = pot.action_order();
+    let step = difficulty.step(pot.big_blind());
+    let round = |amount: f64| (amount / step as f64).round() as u32 * step;
+    let max_actions = rng.gen_range(1..=difficulty.max_actions());
+
+    let mut lines = Vec::new();
+    let mut acted = vec![false; pot.table_size()];
+    let opener = rng.gen_range(0..order.len() - 2);
+    // Everyone before the opener has already folded.
+    for &seat in &order[..opener] {
+        pot.apply((seat, Action::Fold)).unwrap();
+        acted[seat] = true;
+    }
+    for &seat in order.iter().cycle().skip(opener).take(order.len() * 3) {
+        if pot.has_folded(seat) {
+            continue;
+        }
+        // Everyone has matched the last raise, so the action is over.
+        if pot.to_call(seat) == 0 && acted[seat] {
+            break;
+        }
+        let name = pot
+            .position(seat)
+            .map_or(format!("Seat {}", seat + 1), |p| p.to_string());
+        let action = if lines.is_empty() {
+            let amount = match difficulty {
+                Difficulty::Easy => pot.big_blind() * rng.gen_range(2..=3),
+                _ => round(pot.big_blind() as f64 * rng.gen_range(2.0..3.0)),
+            };
+            Action::Open(amount.max(pot.min_raise_to()))
+        } else if lines.len() >= max_actions {
+            Action::Fold
+        } else {
+            match rng.gen_range(0..10) {
+                0..=2 if !acted[seat] || difficulty == Difficulty::Hard => {
+                    let amount = round(pot.current_bet() as f64 * rng.gen_range(2.5..3.5));
+                    Action::ReRaise(amount.max(pot.min_raise_to()))
+                }
+                3..=5 if pot.to_call(seat) > 0 => Action::Call,
+                _ => Action::Fold,
+            }
+        };
+        // The first action is always valid and later ones only depend on the
+        // pot's own numbers, so the engine never rejects them.
+        pot.apply((seat, action)).unwrap();
+        acted[seat] = true;
+        match action {
+            Action::Open(amount) => lines.push(format!("{} opens to {}.", name, amount)),
+            Action::ReRaise(amount) => lines.push(format!("{} re-raises to {}.", name, amount)),
+            Action::Call => lines.push(format!("{} calls.", name)),
+            _ => {}
+        }
+    }
+    lines
+}
+
+/// Asks pot-size and big-blind-count questions.
+pub fn drill(mut session: Session, difficulty: Difficulty) {
+    while session.next() {
+        let question = generate(session.rng(), difficulty);
+        println!();
+        println!("{}", question.prompt);
+
+        let answer: u32 = match session.answer("Please enter a whole number", |input| {
+            input.replace([',', '_'], "").parse().ok()
+        }) {
+            Some(answer) => answer,
+            None => break,
+        };
+        let correct = answer == question.answer;
+        session.record(
+            Outcome::new(&question.prompt, answer, question.answer, correct)
+                .category(question.category),
+        );
+        if correct {
+            println!("Correct!");
+        } else {
+            println!("Not quite. The answer is {}", question.answer);
+        }
+    }
+    session.finish();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fundamentals::position::Position;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            for _ in 0..500 {
+                let question = generate(&mut rng, difficulty);
+                assert!(question.answer > 0, "{}", question.prompt);
+                assert!(question.prompt.ends_with('?'));
+            }
+        }
+    }
+
+    #[test]
+    fn test_play_matches_the_pot() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let antes = [
+            (AnteStructure::None, 0),
+            (AnteStructure::BigBlind(400), 400),
+            (AnteStructure::Button(400), 400),
+            (AnteStructure::PerPlayer(50), 50 * 9),
+        ];
+        for (ante, dead) in antes {
+            for _ in 0..200 {
+                let mut pot = Pot::new(200, 400, ante, 9).unwrap();
+                let lines = play(&mut rng, &mut pot, Difficulty::Hard);
+                assert!(lines[0].contains(" opens to "), "{:?}", lines);
+
+                // Replay the lines on top of the blinds.
+                let mut put_in: HashMap<&str, u32> = HashMap::from([("SB", 200), ("BB", 400)]);
+                let mut current_bet = 400;
+                for line in &lines {
+                    let (name, action) = line.split_once(' ').unwrap();
+                    let amount = match action.trim_end_matches('.').rsplit_once(" to ") {
+                        Some((_, amount)) => {
+                            current_bet = amount.parse().unwrap();
+                            current_bet
+                        }
+                        None => {
+                            assert_eq!(action, "calls.");
+                            current_bet
+                        }
+                    };
+                    put_in.insert(name, amount);
+                }
+                assert_eq!(
+                    put_in.values().sum::<u32>() + dead,
+                    pot.size(),
+                    "{:?}",
+                    lines
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_play_follows_preflop_order() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for table_size in [6, 9] {
+            let table = Position::table(table_size).unwrap();
+            for _ in 0..500 {
+                let mut pot = Pot::new(200, 400, AnteStructure::None, table_size).unwrap();
+                let lines = play(&mut rng, &mut pot, Difficulty::Hard);
+                let actors: Vec<usize> = lines
+                    .iter()
+                    .map(|line| {
+                        let name: Position = line.split(' ').next().unwrap().parse().unwrap();
+                        table.iter().position(|&p| p == name).unwrap()
+                    })
+                    .collect();
+                // Seats ahead of the opener folded before it opened.
+                assert!(actors.iter().all(|&i| i >= actors[0]), "{:?}", lines);
+            }
+        }
+    }
+}
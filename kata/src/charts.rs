@@ -0,0 +1,87 @@
+use crate::history::Outcome;
+use crate::session::Session;
+use preflop::chart::Chart;
+use rand::seq::SliceRandom;
+use std::path::Path;
+
+/// How much more often hands on the edge of a range come up with --boundary.
+const BOUNDARY_WEIGHT: f64 = 4.0;
+
+/// Quizzes the user on a preflop chart, showing the chart on a miss.
+pub fn drill(mut session: Session, path: &Path, boundary: bool) {
+    let chart = match Chart::load(path) {
+        Ok(chart) if !chart.is_empty() => chart,
+        Ok(_) => {
+            println!("{} has no entries", path.display());
+            return;
+        }
+        Err(error) => {
+            println!("{}: {}", path.display(), error);
+            return;
+        }
+    };
+    let weights: Vec<f64> = chart
+        .entries()
+        .iter()
+        .map(|context| match boundary && chart.is_boundary(context) {
+            true => BOUNDARY_WEIGHT,
+            false => 1.0,
+        })
+        .collect();
+
+    let indices: Vec<usize> = (0..weights.len()).collect();
+    while session.next() {
+        let index = *indices
+            .choose_weighted(session.rng(), |&i| weights[i])
+            .unwrap();
+        let context = &chart.entries()[index];
+        println!();
+        println!("{}", context);
+        println!("[r] raise  [c] call  [f] fold  [j] jam");
+
+        let (input, grade) = match session.answer("Please enter r, c, f or j", |input| {
+            Some((input.to_string(), context.grade(input)?))
+        }) {
+            Some(answer) => answer,
+            None => break,
+        };
+        let outcome = Outcome::new(
+            context,
+            &input,
+            context.get_expected_action(),
+            grade.is_correct(),
+        )
+        .category(context.scenario())
+        .position(
+            context
+                .position()
+                .map_or(context.table_position().to_string(), |p| p.to_string()),
+        )
+        .stack(context.stack_size())
+        .score(grade.score);
+        session.record(outcome);
+        match grade.is_correct() {
+            true => {
+                if context.strategy().is_mixed() {
+                    println!("Correct! The chart plays {}", context.get_expected_action());
+                } else {
+                    println!("Correct!");
+                }
+            }
+            false => {
+                println!(
+                    "Not quite. The chart plays {}",
+                    context.get_expected_action()
+                );
+                println!();
+                print!("{}", chart.grid(context).render(session.colored()));
+            }
+        }
+        println!(
+            "Frequency score {:.2}: the chart plays that {:.0}% of the time",
+            grade.score,
+            grade.frequency * 100.0
+        );
+    }
+    session.finish();
+}
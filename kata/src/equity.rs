@@ -0,0 +1,61 @@
+use crate::history::Outcome;
+use crate::session::Session;
+use nuts::equity::{equity, EquityOptions};
+use nuts::Deck;
+use rand::Rng;
+use utils::card::CardList;
+
+/// Boards the equity drill deals: preflop, flop and turn.
+const STREETS: [(&str, usize); 3] = [("preflop", 0), ("flop", 3), ("turn", 4)];
+
+/// Deals two hands and a board and asks for the first hand's equity,
+/// accepting answers within `tolerance` percentage points.
+pub fn drill(mut session: Session, tolerance: f64) {
+    let mut deck = Deck::from_seed(session.rng().gen());
+    while session.next() {
+        deck.reset();
+        deck.shuffle();
+        let cards = deck.deal(8).unwrap();
+        let hero = (cards[0], cards[1]);
+        let villain = (cards[2], cards[3]);
+        let (street, board_size) = STREETS[session.rng().gen_range(0..STREETS.len())];
+        let board = &cards[4..4 + board_size];
+
+        let mut question = format!(
+            "{} vs {}",
+            CardList::from(vec![hero.0, hero.1]),
+            CardList::from(vec![villain.0, villain.1])
+        );
+        if !board.is_empty() {
+            question += &format!("\nBoard: {}", CardList::from(board.to_vec()));
+        }
+        println!();
+        println!("{}", question);
+        println!("How much equity does the first hand have? (%)");
+
+        let options = EquityOptions {
+            samples: 20_000,
+            seed: Some(session.rng().gen()),
+            ..Default::default()
+        };
+        let expected =
+            equity(&[vec![hero], vec![villain]], board, &[], &options).unwrap()[0].equity * 100.0;
+
+        let answer: f64 = match session.answer("Please enter a number", |input| {
+            input.trim_end_matches('%').trim().parse().ok()
+        }) {
+            Some(answer) => answer,
+            None => break,
+        };
+        let correct = (answer - expected).abs() <= tolerance;
+        session.record(
+            Outcome::new(question, answer, format!("{expected:.1}"), correct).category(street),
+        );
+        if correct {
+            println!("Correct! The first hand has {expected:.1}%");
+        } else {
+            println!("Not quite. The first hand has {expected:.1}%");
+        }
+    }
+    session.finish();
+}
@@ -0,0 +1,61 @@
+use crate::history::Outcome;
+use crate::session::Session;
+use nuts::{Deck, HandRank};
+use rand::Rng;
+use utils::card::CardList;
+
+const HAND_RANKS: [HandRank; 10] = [
+    HandRank::HighCard,
+    HandRank::OnePair,
+    HandRank::TwoPair,
+    HandRank::ThreeOfAKind,
+    HandRank::Straight,
+    HandRank::Flush,
+    HandRank::FullHouse,
+    HandRank::FourOfAKind,
+    HandRank::StraightFlush,
+    HandRank::RoyalFlush,
+];
+
+/// Deals five community cards and asks for the nuts they allow.
+pub fn drill(mut session: Session) {
+    let mut deck = Deck::from_seed(session.rng().gen());
+    while session.next() {
+        deck.reset();
+        deck.shuffle();
+        let hand = nuts::Hand::deal(&mut deck, 5);
+        let mut cards = hand.cards.clone();
+        cards.sort();
+        let board = CardList::from(cards).to_string();
+        println!();
+        println!("{}", board);
+        println!();
+        for (key, hand_rank) in HAND_RANKS.iter().enumerate() {
+            println!("[{key}] {hand_rank}");
+        }
+
+        let actual = match session.answer("Please enter a number from 0 to 9", |input| {
+            input
+                .parse::<usize>()
+                .ok()
+                .and_then(|key| HAND_RANKS.get(key))
+        }) {
+            Some(actual) => *actual,
+            None => break,
+        };
+
+        let (expected, nuts) = nuts::find_nuts(&hand.cards);
+        let correct = actual == expected;
+        let nuts = CardList::from(nuts.cards);
+        if correct {
+            println!("Correct!");
+        } else {
+            println!("Not quite. The nuts are {}: {}", expected, nuts);
+        }
+        session.record(
+            Outcome::new(board, actual, format!("{}: {}", expected, nuts), correct)
+                .category(expected),
+        );
+    }
+    session.finish();
+}
@@ -0,0 +1,176 @@
+//! Every answered question, kept between sessions as JSON lines.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// One answered question.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub skill: String,
+    pub question: String,
+    pub answer: String,
+    pub expected: String,
+    pub correct: bool,
+    /// Seconds taken to answer.
+    pub response_time: f64,
+    /// What kind of question it was within the skill, like the nut hand's
+    /// rank.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<String>,
+    /// Partial credit between 0 and 1, for drills that grade answers by
+    /// more than right or wrong.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+}
+
+/// A drill's verdict on one answer, before the session stamps it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub question: String,
+    pub answer: String,
+    pub expected: String,
+    pub correct: bool,
+    pub category: Option<String>,
+    pub position: Option<String>,
+    pub stack: Option<String>,
+    pub score: Option<f64>,
+}
+
+impl Outcome {
+    pub fn new<Q, A, E>(question: Q, answer: A, expected: E, correct: bool) -> Outcome
+    where
+        Q: ToString,
+        A: ToString,
+        E: ToString,
+    {
+        Outcome {
+            question: question.to_string(),
+            answer: answer.to_string(),
+            expected: expected.to_string(),
+            correct,
+            category: None,
+            position: None,
+            stack: None,
+            score: None,
+        }
+    }
+
+    pub fn category<T: ToString>(mut self, category: T) -> Outcome {
+        self.category = Some(category.to_string());
+        self
+    }
+
+    pub fn position<T: ToString>(mut self, position: T) -> Outcome {
+        self.position = Some(position.to_string());
+        self
+    }
+
+    pub fn stack<T: ToString>(mut self, stack: T) -> Outcome {
+        self.stack = Some(stack.to_string());
+        self
+    }
+
+    pub fn score(mut self, score: f64) -> Outcome {
+        self.score = Some(score);
+        self
+    }
+}
+
+/// An append-only file of [`Record`]s, one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub fn new<P: Into<PathBuf>>(path: P) -> History {
+        History { path: path.into() }
+    }
+
+    /// `kata/history.jsonl` in the user's data directory, if there is one.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("kata").join("history.jsonl"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, record: &Record) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let json = serde_json::to_string(record).map_err(io::Error::from)?;
+        writeln!(file, "{}", json)
+    }
+
+    /// Every record so far, oldest first. A missing file is an empty
+    /// history, and lines that don't parse, like one cut short by a crash,
+    /// are skipped.
+    pub fn load(&self) -> io::Result<Vec<Record>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            if let Ok(record) = serde_json::from_str(&line?) {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("kata-history-{}", std::process::id()))
+            .join("history.jsonl");
+        let history = History::new(&path);
+        assert_eq!(history.load().unwrap(), Vec::new());
+
+        let record = Record {
+            time: 1_700_000_000,
+            skill: "nuts".to_string(),
+            question: "2c 4h 7h 9d Ad".to_string(),
+            answer: "Straight".to_string(),
+            expected: "Straight".to_string(),
+            correct: true,
+            response_time: 2.5,
+            category: Some("Straight".to_string()),
+            position: None,
+            stack: None,
+            score: None,
+        };
+        history.append(&record).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{{\"time\":"))
+            .unwrap();
+        history.append(&record).unwrap();
+
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(!json.contains("position"));
+        assert_eq!(history.load().unwrap(), vec![record.clone(), record]);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
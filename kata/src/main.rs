@@ -1,71 +1,84 @@
-use clap::Parser;
-use nuts::HandRank;
-use std::collections::HashMap;
-use std::io;
+mod arithmetic;
+mod charts;
+mod equity;
+mod hands;
+mod history;
+mod odds;
+mod session;
+mod stats;
+
+use arithmetic::Difficulty;
+use clap::{Parser, Subcommand};
+use session::{Session, SessionOptions};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
-struct Args {
-    #[arg(short, long)]
-    skill: String,
+#[command(about = "Daily poker drills")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    #[command(flatten)]
+    session: SessionOptions,
 }
 
-fn main() {
-    let args = Args::parse();
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Name the best hand five cards make.
+    Nuts,
+    /// Answer from a preflop chart.
+    Preflop {
+        /// Preflop chart to drill, one entry per line.
+        #[arg(short, long)]
+        chart: PathBuf,
 
-    if args.skill == "nuts" {
-        nuts();
-    }
+        /// Ask more often about hands on the edge of a range.
+        #[arg(short, long)]
+        boundary: bool,
+    },
+    /// Count the pot and stacks in big blinds.
+    Pot {
+        /// How hard the questions are.
+        #[arg(short, long, value_enum, default_value_t = Difficulty::Medium)]
+        difficulty: Difficulty,
+    },
+    /// Estimate pot odds, required equity, bluff break-even and MDF.
+    Odds {
+        /// How far off an estimate may be, in percentage points.
+        #[arg(long, default_value_t = 3.0)]
+        tolerance: f64,
+    },
+    /// Estimate one hand's equity against another.
+    Equity {
+        /// How far off an estimate may be, in percentage points.
+        #[arg(long, default_value_t = 5.0)]
+        tolerance: f64,
+    },
+    /// Report accuracy from the answer history.
+    Stats,
 }
 
-fn nuts() {
-    let hand_ranks: HashMap<usize, HandRank> = HashMap::from([
-        (0, HandRank::HighCard),
-        (1, HandRank::OnePair),
-        (2, HandRank::TwoPair),
-        (3, HandRank::ThreeOfAKind),
-        (4, HandRank::Straight),
-        (5, HandRank::Flush),
-        (6, HandRank::FullHouse),
-        (7, HandRank::FourOfAKind),
-        (8, HandRank::StraightFlush),
-        (9, HandRank::RoyalFlush),
-    ]);
+fn main() {
+    let cli = Cli::parse();
 
-    loop {
-        let hand = nuts::Hand::random(5);
-        let cards = hand.cards.clone();
-        println!();
-        for card in cards {
-            print!("{card}\t");
+    match cli.command {
+        Command::Nuts => hands::drill(Session::new("nuts", cli.session)),
+        Command::Preflop { chart, boundary } => {
+            charts::drill(Session::new("preflop", cli.session), &chart, boundary)
         }
-        println!();
-        println!();
-        for (key, hand_rank) in &hand_ranks {
-            println!("[{key}] {hand_rank}");
+        Command::Pot { difficulty } => {
+            arithmetic::drill(Session::new("pot", cli.session), difficulty)
         }
-
-        let stdin = io::stdin();
-        let mut input = String::new();
-        stdin.read_line(&mut input).expect("Failed to read line");
-
-        let key: usize = match input.trim().parse() {
-            Ok(key) => key,
-            Err(_) => {
-                println!("Invalid input. Please enter a valid number");
-                return;
-            }
-        };
-
-        match hand_ranks.get(&key) {
-            Some(actual) => {
-                let community_cards = hand.cards.clone();
-                let expected = nuts::find_nuts(community_cards.as_slice());
-                assert_eq!(actual.clone(), expected.0);
-            }
-            None => println!("Invalid input. Please enter a valid number"),
-        };
-
-        println!();
-        println!();
+        Command::Odds { tolerance } => odds::drill(Session::new("odds", cli.session), tolerance),
+        Command::Equity { tolerance } => {
+            equity::drill(Session::new("equity", cli.session), tolerance)
+        }
+        Command::Stats => match cli.session.history() {
+            Some(history) => match history.load() {
+                Ok(records) => print!("{}", stats::report(&records)),
+                Err(error) => eprintln!("{}: {}", history.path().display(), error),
+            },
+            None => eprintln!("No data directory for the history; pass --stats"),
+        },
     }
 }
@@ -0,0 +1,86 @@
+use crate::history::Outcome;
+use crate::session::Session;
+use fundamentals::odds;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Bet sizes offered by the odds drill, as fractions of the pot.
+const BET_SIZES: [(u32, u32); 7] = [(1, 4), (1, 3), (1, 2), (2, 3), (3, 4), (1, 1), (3, 2)];
+
+/// The one question answered as a ratio rather than a percentage.
+const POT_ODDS: &str = "pot odds";
+
+/// Asks for pot odds, required equity, bluff break-even and MDF, accepting
+/// answers within `tolerance` percentage points.
+pub fn drill(mut session: Session, tolerance: f64) {
+    while session.next() {
+        let rng = session.rng();
+        let pot = rng.gen_range(4..=100) * 100;
+        let (numerator, denominator) = *BET_SIZES.choose(rng).unwrap();
+        let bet = pot * numerator / denominator;
+
+        println!();
+        println!("There is {pot} in the pot and the bet is {bet}.");
+        let (category, question, expected) = match rng.gen_range(0..4) {
+            0 => (
+                POT_ODDS,
+                "What pot odds is the caller getting? (x to 1)",
+                odds::pot_odds(pot + bet, bet),
+            ),
+            1 => (
+                "required equity",
+                "How much equity does the caller need? (%)",
+                odds::required_equity(pot + bet, bet).map(|equity| equity * 100.0),
+            ),
+            2 => (
+                "bluff break-even",
+                "How often must a bluff work to break even? (%)",
+                odds::bluff_break_even(pot, bet).map(|frequency| frequency * 100.0),
+            ),
+            _ => (
+                "minimum defense frequency",
+                "How often must the caller defend? (%)",
+                odds::minimum_defense_frequency(pot, bet).map(|frequency| frequency * 100.0),
+            ),
+        };
+        let expected = expected.expect("the bet is never zero");
+        println!("{question}");
+
+        // Accept "25", "25%" and "3:1".
+        let answer: f64 = match session.answer("Please enter a number", |input| {
+            let text = input.trim_end_matches('%');
+            text.split(':')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .parse()
+                .ok()
+        }) {
+            Some(answer) => answer,
+            None => break,
+        };
+
+        // Pot odds are a ratio, so the tolerance is relative to the answer.
+        let allowed = if category == POT_ODDS {
+            expected * tolerance / 100.0
+        } else {
+            tolerance
+        };
+        let correct = (answer - expected).abs() <= allowed;
+        session.record(
+            Outcome::new(
+                format!("Pot {pot}, bet {bet}. {question}"),
+                answer,
+                format!("{expected:.1}"),
+                correct,
+            )
+            .category(category),
+        );
+        if correct {
+            println!("Correct! The exact answer is {expected:.1}");
+        } else {
+            println!("Not quite. The exact answer is {expected:.1}");
+        }
+    }
+    session.finish();
+}
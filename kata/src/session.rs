@@ -0,0 +1,310 @@
+//! Plumbing shared by every drill: how long a session runs, where its
+//! randomness comes from, reading and recording answers and reporting the
+//! score.
+
+use crate::history::{History, Outcome, Record};
+use clap::{Args, ValueEnum};
+use is_terminal::IsTerminal;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Serialize;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct SessionOptions {
+    /// Stop after this many questions.
+    #[arg(short = 'n', long, global = true)]
+    pub questions: Option<usize>,
+
+    /// Seed the questions so a session can be repeated.
+    #[arg(long, global = true)]
+    pub seed: Option<u64>,
+
+    /// Stop after this many seconds.
+    #[arg(short, long, global = true)]
+    pub time_limit: Option<u64>,
+
+    /// How to print the summary at the end of the session.
+    #[arg(short, long, value_enum, global = true, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// Record every answer in this file instead of the default history in
+    /// the user's data directory.
+    #[arg(long, global = true)]
+    pub stats: Option<PathBuf>,
+}
+
+impl SessionOptions {
+    /// Where answers are recorded, if anywhere.
+    pub fn history(&self) -> Option<History> {
+        self.stats
+            .clone()
+            .or_else(History::default_path)
+            .map(History::new)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    pub skill: String,
+    pub questions: usize,
+    pub correct: usize,
+    pub accuracy: f64,
+    /// Mean partial credit, for drills that give it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    pub best_streak: usize,
+    /// Mean time taken to answer, in seconds.
+    pub average_time: f64,
+    pub seconds: u64,
+}
+
+pub struct Session {
+    skill: &'static str,
+    options: SessionOptions,
+    rng: StdRng,
+    history: Option<History>,
+    start: Instant,
+    asked: usize,
+    correct: usize,
+    streak: usize,
+    best_streak: usize,
+    /// Answers given partial credit, and their total.
+    scored: usize,
+    total_score: f64,
+    /// How long the last question took to answer.
+    response_time: Duration,
+    total_response_time: Duration,
+}
+
+impl Session {
+    pub fn new(skill: &'static str, options: SessionOptions) -> Session {
+        let rng = match options.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Session {
+            skill,
+            history: options.history(),
+            options,
+            rng,
+            start: Instant::now(),
+            asked: 0,
+            correct: 0,
+            streak: 0,
+            best_streak: 0,
+            scored: 0,
+            total_score: 0.0,
+            response_time: Duration::ZERO,
+            total_response_time: Duration::ZERO,
+        }
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Whether output may use ANSI colours: only for text output to a
+    /// terminal.
+    pub fn colored(&self) -> bool {
+        self.options.format == Format::Text && io::stdout().is_terminal()
+    }
+
+    /// Whether there is time and room for another question.
+    pub fn next(&self) -> bool {
+        if matches!(self.options.questions, Some(limit) if self.asked >= limit) {
+            return false;
+        }
+        if let Some(limit) = self.options.time_limit {
+            if self.start.elapsed() >= Duration::from_secs(limit) {
+                println!("Time's up!");
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Reads one line of input, or `None` if the user typed `q` or the
+    /// input ended.
+    fn read_line(&self) -> Option<String> {
+        let mut input = String::new();
+        match io::stdin().read_line(&mut input) {
+            Ok(0) | Err(_) => None,
+            Ok(_) if input.trim() == "q" => None,
+            Ok(_) => Some(input.trim().to_string()),
+        }
+    }
+
+    /// Reads answers until `parse` accepts one, printing `hint` after each
+    /// it rejects, or `None` if the user quits. The response time runs from
+    /// the first prompt to the accepted answer, retries included.
+    pub fn answer<T, F: FnMut(&str) -> Option<T>>(
+        &mut self,
+        hint: &str,
+        mut parse: F,
+    ) -> Option<T> {
+        let asked = Instant::now();
+        loop {
+            let input = self.read_line()?;
+            if let Some(answer) = parse(&input) {
+                self.response_time = asked.elapsed();
+                return Some(answer);
+            }
+            println!("Invalid input. {}", hint);
+        }
+    }
+
+    /// Scores the last answer, adds it to the history and prints the
+    /// running score.
+    pub fn record(&mut self, outcome: Outcome) {
+        let correct = outcome.correct;
+        self.asked += 1;
+        self.total_response_time += self.response_time;
+        if let Some(score) = outcome.score {
+            self.scored += 1;
+            self.total_score += score;
+        }
+        self.save(outcome);
+        if correct {
+            self.correct += 1;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.streak = 0;
+        }
+        let summary = self.summary();
+        println!(
+            "Score {}/{} ({:.0}%), streak {}, {:.1}s per answer",
+            summary.correct,
+            summary.questions,
+            summary.accuracy * 100.0,
+            self.streak,
+            summary.average_time
+        );
+    }
+
+    fn save(&mut self, outcome: Outcome) {
+        let history = match &self.history {
+            Some(history) => history,
+            None => return,
+        };
+        let record = Record {
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_secs()),
+            skill: self.skill.to_string(),
+            question: outcome.question,
+            answer: outcome.answer,
+            expected: outcome.expected,
+            correct: outcome.correct,
+            response_time: self.response_time.as_secs_f64(),
+            category: outcome.category,
+            position: outcome.position,
+            stack: outcome.stack,
+            score: outcome.score,
+        };
+        if let Err(error) = history.append(&record) {
+            // Warn once rather than after every answer.
+            eprintln!(
+                "Could not record answers in {}: {}",
+                history.path().display(),
+                error
+            );
+            self.history = None;
+        }
+    }
+
+    pub fn summary(&self) -> Summary {
+        let (accuracy, average_time) = match self.asked {
+            0 => (0.0, 0.0),
+            asked => (
+                self.correct as f64 / asked as f64,
+                self.total_response_time.as_secs_f64() / asked as f64,
+            ),
+        };
+        Summary {
+            skill: self.skill.to_string(),
+            questions: self.asked,
+            correct: self.correct,
+            accuracy,
+            score: match self.scored {
+                0 => None,
+                scored => Some(self.total_score / scored as f64),
+            },
+            best_streak: self.best_streak,
+            average_time,
+            seconds: self.start.elapsed().as_secs(),
+        }
+    }
+
+    /// Prints the summary.
+    pub fn finish(self) {
+        let summary = self.summary();
+        let json = serde_json::to_string(&summary).unwrap();
+        println!();
+        match self.options.format {
+            Format::Text => {
+                println!(
+                    "{} of {} correct ({:.0}%) in {}s",
+                    summary.correct,
+                    summary.questions,
+                    summary.accuracy * 100.0,
+                    summary.seconds
+                );
+                if let Some(score) = summary.score {
+                    println!("Average frequency score {:.2}", score);
+                }
+                println!(
+                    "Best streak {}, {:.1}s per answer",
+                    summary.best_streak, summary.average_time
+                );
+            }
+            Format::Json => println!("{}", json),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_question_limit() {
+        let path = std::env::temp_dir().join(format!("kata-session-{}.jsonl", std::process::id()));
+        let options = SessionOptions {
+            questions: Some(2),
+            stats: Some(path.clone()),
+            ..Default::default()
+        };
+        let mut session = Session::new("pot", options);
+        assert!(session.next());
+        session.record(Outcome::new("Blinds 50-100", "150", "150", true).score(1.0));
+        session.record(Outcome::new("Blinds 50-100", "250", "275", false).category("pot size"));
+        assert!(!session.next());
+
+        let records = History::new(&path).load().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].skill, "pot");
+        assert_eq!(records[1].answer, "250");
+        assert_eq!(records[1].category.as_deref(), Some("pot size"));
+
+        let summary = session.summary();
+        assert_eq!((summary.questions, summary.correct), (2, 1));
+        assert_eq!(summary.accuracy, 0.5);
+        assert_eq!(summary.best_streak, 1);
+        assert_eq!(summary.score, Some(1.0));
+        assert!(serde_json::to_string(&summary)
+            .unwrap()
+            .starts_with(r#"{"skill":"pot","questions":2,"correct":1,"#));
+    }
+}
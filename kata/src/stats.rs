@@ -0,0 +1,181 @@
+//! The `kata stats` report: accuracy by skill, by kind of question, by
+//! preflop position and stack, and day by day.
+
+use crate::history::Record;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Tally {
+    pub questions: usize,
+    pub correct: usize,
+    pub response_time: f64,
+}
+
+impl Tally {
+    fn add(&mut self, record: &Record) {
+        self.questions += 1;
+        if record.correct {
+            self.correct += 1;
+        }
+        self.response_time += record.response_time;
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        self.correct as f64 / self.questions as f64
+    }
+
+    pub fn average_time(&self) -> f64 {
+        self.response_time / self.questions as f64
+    }
+}
+
+/// Tallies the records with a key, skipping those without one.
+pub fn tally<K: Ord, F: Fn(&Record) -> Option<K>>(
+    records: &[Record],
+    key: F,
+) -> BTreeMap<K, Tally> {
+    let mut tallies = BTreeMap::new();
+    for record in records {
+        if let Some(key) = key(record) {
+            tallies
+                .entry(key)
+                .or_insert_with(Tally::default)
+                .add(record);
+        }
+    }
+    tallies
+}
+
+/// The UTC date of a Unix timestamp, as YYYY-MM-DD.
+pub fn date(time: u64) -> String {
+    // Howard Hinnant's civil_from_days, shifted so years start in March.
+    let days = (time / SECONDS_PER_DAY) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+pub fn report(records: &[Record]) -> String {
+    if records.is_empty() {
+        return "No questions answered yet\n".to_string();
+    }
+    let mut out = String::new();
+    section(
+        &mut out,
+        "By skill",
+        tally(records, |r| Some(r.skill.clone())),
+    );
+
+    // Weakest categories first, so the most missed questions stand out.
+    let mut categories: Vec<_> = tally(records, |r| {
+        r.category
+            .as_ref()
+            .map(|category| format!("{} {}", r.skill, category))
+    })
+    .into_iter()
+    .collect();
+    categories.sort_by(|(_, a), (_, b)| a.accuracy().total_cmp(&b.accuracy()));
+    section(&mut out, "By kind of question", categories);
+
+    let preflop = |r: &Record| r.skill == "preflop";
+    section(
+        &mut out,
+        "Preflop by position",
+        tally(records, |r| r.position.clone().filter(|_| preflop(r))),
+    );
+    section(
+        &mut out,
+        "Preflop by stack",
+        tally(records, |r| r.stack.clone().filter(|_| preflop(r))),
+    );
+    section(&mut out, "By day", tally(records, |r| Some(date(r.time))));
+    out
+}
+
+fn section<I: IntoIterator<Item = (String, Tally)>>(out: &mut String, title: &str, rows: I) {
+    let rows: Vec<_> = rows.into_iter().collect();
+    if rows.is_empty() {
+        return;
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    writeln!(out, "{}", title).unwrap();
+    for (name, tally) in rows {
+        writeln!(
+            out,
+            "  {:<28}{:>5} asked{:>5.0}%{:>7.1}s",
+            name,
+            tally.questions,
+            tally.accuracy() * 100.0,
+            tally.average_time()
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(time: u64, skill: &str, category: &str, correct: bool) -> Record {
+        Record {
+            time,
+            skill: skill.to_string(),
+            question: String::new(),
+            answer: String::new(),
+            expected: String::new(),
+            correct,
+            response_time: 3.0,
+            category: Some(category.to_string()),
+            position: Some("BTN".to_string()).filter(|_| skill == "preflop"),
+            stack: Some("deep".to_string()).filter(|_| skill == "preflop"),
+            score: None,
+        }
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(951_782_400), "2000-02-29");
+        assert_eq!(date(1_700_000_000), "2023-11-14");
+    }
+
+    #[test]
+    fn test_report() {
+        let day = 1_700_000_000;
+        let records = vec![
+            record(day, "nuts", "Flush", false),
+            record(day, "nuts", "Flush", true),
+            record(day, "nuts", "Straight", true),
+            record(day + SECONDS_PER_DAY, "preflop", "unopened", false),
+        ];
+        let skills = tally(&records, |r| Some(r.skill.clone()));
+        assert_eq!(skills["nuts"].questions, 3);
+        assert_eq!(skills["nuts"].correct, 2);
+        assert_eq!(skills["preflop"].accuracy(), 0.0);
+
+        let report = report(&records);
+        assert!(report.starts_with("By skill\n  nuts"));
+        let flush = report.find("nuts Flush").unwrap();
+        assert!(report.find("preflop unopened").unwrap() < flush);
+        assert!(flush < report.find("nuts Straight").unwrap());
+        assert!(report.contains("Preflop by position\n  BTN"));
+        assert!(report.contains("By day\n  2023-11-14"));
+        assert!(report.contains("  2023-11-15"));
+    }
+}
@@ -0,0 +1,417 @@
+//! All-in equity between two or more players.
+//!
+//! Each player is given as the list of hole cards they may hold: a single
+//! holding is a list of one, a range is a list of many. When the number of
+//! matchups times the number of ways to complete the board is small enough the
+//! result is exact, otherwise it is estimated by Monte Carlo sampling.
+
+use crate::evaluator;
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use utils::card::Card;
+use utils::deck::Deck;
+
+/// Two hole cards.
+pub type Holding = (Card, Card);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquityOptions {
+    /// Largest number of showdowns to enumerate before switching to sampling.
+    pub exhaustive_limit: u64,
+    /// Number of showdowns to sample when not enumerating.
+    pub samples: usize,
+    /// Seed for sampling; `None` draws one from the operating system.
+    pub seed: Option<u64>,
+}
+
+impl Default for EquityOptions {
+    fn default() -> Self {
+        EquityOptions {
+            exhaustive_limit: 1_000_000,
+            samples: 100_000,
+            seed: None,
+        }
+    }
+}
+
+/// How one player fared over every showdown considered.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Equity {
+    /// Share of showdowns won outright.
+    pub win: f64,
+    /// Share of showdowns split with at least one other player.
+    pub tie: f64,
+    /// Share of showdowns lost.
+    pub lose: f64,
+    /// Share of the pot won on average, counting a split as a fraction.
+    pub equity: f64,
+}
+
+impl Display for Equity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "equity {:.2}% (win {:.2}%, tie {:.2}%, lose {:.2}%)",
+            self.equity * 100.0,
+            self.win * 100.0,
+            self.tie * 100.0,
+            self.lose * 100.0
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquityError {
+    NotEnoughPlayers,
+    BoardTooLong(usize),
+    DuplicateCard(Card),
+    /// No combination of the players' holdings avoids sharing a card.
+    NoValidMatchup,
+}
+
+impl Display for EquityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EquityError::NotEnoughPlayers => write!(f, "At least two players are needed"),
+            EquityError::BoardTooLong(n) => write!(f, "Board has {} cards, at most 5 allowed", n),
+            EquityError::DuplicateCard(card) => write!(f, "Duplicate card: {}", card),
+            EquityError::NoValidMatchup => write!(f, "Every matchup shares a card"),
+        }
+    }
+}
+
+impl Error for EquityError {}
+
+/// Computes each player's equity given a partial `board` and `dead` cards.
+///
+/// Holdings that share a card with the board, the dead cards or each other
+/// are skipped.
+pub fn equity(
+    players: &[Vec<Holding>],
+    board: &[Card],
+    dead: &[Card],
+    options: &EquityOptions,
+) -> Result<Vec<Equity>, EquityError> {
+    if players.len() < 2 {
+        return Err(EquityError::NotEnoughPlayers);
+    }
+    if board.len() > 5 {
+        return Err(EquityError::BoardTooLong(board.len()));
+    }
+
+    let mut known: Vec<Card> = Vec::with_capacity(board.len() + dead.len());
+    for card in board.iter().chain(dead) {
+        if known.contains(card) {
+            return Err(EquityError::DuplicateCard(*card));
+        }
+        known.push(*card);
+    }
+
+    let players: Vec<Vec<Holding>> = players
+        .iter()
+        .map(|holdings| {
+            holdings
+                .iter()
+                .copied()
+                .filter(|(a, b)| a != b && !known.contains(a) && !known.contains(b))
+                .collect()
+        })
+        .collect();
+    if players.iter().any(|holdings| holdings.is_empty()) {
+        return Err(EquityError::NoValidMatchup);
+    }
+
+    let missing = 5 - board.len();
+    let unseen = 52usize.saturating_sub(known.len() + 2 * players.len());
+    let matchups: u64 = players.iter().map(|h| h.len() as u64).product();
+    let showdowns = matchups.saturating_mul(choose(unseen as u64, missing as u64));
+
+    let mut tally = Tally::new(players.len());
+    if showdowns <= options.exhaustive_limit {
+        enumerate(&players, board, &known, &mut Vec::new(), &mut tally);
+    } else {
+        sample(&players, board, &known, options, &mut tally);
+    }
+
+    if tally.showdowns == 0 {
+        return Err(EquityError::NoValidMatchup);
+    }
+    Ok(tally.finish())
+}
+
+struct Tally {
+    showdowns: u64,
+    wins: Vec<u64>,
+    ties: Vec<u64>,
+    shares: Vec<f64>,
+}
+
+impl Tally {
+    fn new(players: usize) -> Tally {
+        Tally {
+            showdowns: 0,
+            wins: vec![0; players],
+            ties: vec![0; players],
+            shares: vec![0.0; players],
+        }
+    }
+
+    fn showdown(&mut self, holdings: &[Holding], board: &[Card]) {
+        let mut cards = [board[0]; 7];
+        cards[..5].copy_from_slice(board);
+        let values: Vec<_> = holdings
+            .iter()
+            .map(|&(a, b)| {
+                cards[5] = a;
+                cards[6] = b;
+                evaluator::evaluate(&cards)
+            })
+            .collect();
+
+        let best = *values.iter().max().unwrap();
+        let winners = values.iter().filter(|&&value| value == best).count();
+        for (i, value) in values.iter().enumerate() {
+            if *value == best {
+                if winners == 1 {
+                    self.wins[i] += 1;
+                } else {
+                    self.ties[i] += 1;
+                }
+                self.shares[i] += 1.0 / winners as f64;
+            }
+        }
+        self.showdowns += 1;
+    }
+
+    fn finish(self) -> Vec<Equity> {
+        let total = self.showdowns as f64;
+        (0..self.wins.len())
+            .map(|i| {
+                let win = self.wins[i] as f64 / total;
+                let tie = self.ties[i] as f64 / total;
+                Equity {
+                    win,
+                    tie,
+                    lose: 1.0 - win - tie,
+                    equity: self.shares[i] / total,
+                }
+            })
+            .collect()
+    }
+}
+
+fn enumerate(
+    players: &[Vec<Holding>],
+    board: &[Card],
+    known: &[Card],
+    chosen: &mut Vec<Holding>,
+    tally: &mut Tally,
+) {
+    if chosen.len() == players.len() {
+        let remaining: Vec<Card> = Card::all()
+            .filter(|card| !known.contains(card) && !holds(chosen, card))
+            .collect();
+        let mut full_board = board.to_vec();
+        for runout in remaining.into_iter().combinations(5 - board.len()) {
+            full_board.truncate(board.len());
+            full_board.extend(runout);
+            tally.showdown(chosen, &full_board);
+        }
+        return;
+    }
+
+    for &(a, b) in &players[chosen.len()] {
+        if holds(chosen, &a) || holds(chosen, &b) {
+            continue;
+        }
+        chosen.push((a, b));
+        enumerate(players, board, known, chosen, tally);
+        chosen.pop();
+    }
+}
+
+/// Skip a sample after this many attempts to pick non-overlapping
+/// holdings, which only happens with heavily overlapping ranges.
+const MAX_ATTEMPTS: usize = 1_000;
+
+/// Plays out `options.samples` random showdowns, skipping any sample that
+/// can't find non-overlapping holdings. If the first sample already fails
+/// there is most likely no valid matchup, so sampling stops with nothing
+/// tallied.
+fn sample(
+    players: &[Vec<Holding>],
+    board: &[Card],
+    known: &[Card],
+    options: &EquityOptions,
+    tally: &mut Tally,
+) {
+    let mut rng = StdRng::seed_from_u64(options.seed.unwrap_or_else(rand::random));
+    let mut deck = Deck::from_seed(rng.gen());
+    deck.remove(known);
+
+    let mut chosen: Vec<Holding> = Vec::with_capacity(players.len());
+    let mut full_board = board.to_vec();
+
+    'samples: for _ in 0..options.samples {
+        let mut attempts = 0;
+        chosen.clear();
+        while chosen.len() < players.len() {
+            let holdings = &players[chosen.len()];
+            let (a, b) = holdings[rng.gen_range(0..holdings.len())];
+            if holds(&chosen, &a) || holds(&chosen, &b) {
+                attempts += 1;
+                if attempts == MAX_ATTEMPTS {
+                    if tally.showdowns == 0 {
+                        return;
+                    }
+                    continue 'samples;
+                }
+                chosen.clear();
+                continue;
+            }
+            chosen.push((a, b));
+        }
+
+        deck.reset();
+        deck.shuffle();
+        full_board.truncate(board.len());
+        while full_board.len() < 5 {
+            let card = deck.deal_one().unwrap();
+            if !holds(&chosen, &card) {
+                full_board.push(card);
+            }
+        }
+        tally.showdown(&chosen, &full_board);
+    }
+}
+
+fn holds(holdings: &[Holding], card: &Card) -> bool {
+    holdings.iter().any(|(a, b)| a == card || b == card)
+}
+
+fn choose(n: u64, k: u64) -> u64 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::card::CardList;
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.parse::<CardList>().unwrap().into_inner()
+    }
+
+    fn holding(s: &str) -> Vec<Holding> {
+        let cards = cards(s);
+        vec![(cards[0], cards[1])]
+    }
+
+    #[test]
+    fn test_exhaustive_turn() {
+        // Nine hearts, three aces and three kings give the flush draw 15 outs.
+        let result = equity(
+            &[holding("AhKh"), holding("QsQc")],
+            &cards("2h 7h 9c 3d"),
+            &[],
+            &EquityOptions::default(),
+        )
+        .unwrap();
+
+        assert!((result[0].win - 15.0 / 44.0).abs() < 1e-9);
+        assert!((result[1].win - 29.0 / 44.0).abs() < 1e-9);
+        assert_eq!(result[0].tie, 0.0);
+        assert!((result[0].equity + result[1].equity - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dead_cards_and_ties() {
+        let result = equity(
+            &[holding("AhKd"), holding("AsKc")],
+            &cards("2h 7h 9h 3d"),
+            &cards("Ks"),
+            &EquityOptions::default(),
+        )
+        .unwrap();
+
+        // Only a heart river breaks the tie: 9 of 43 unseen cards.
+        assert!((result[0].win - 9.0 / 43.0).abs() < 1e-9);
+        assert!((result[0].tie - 34.0 / 43.0).abs() < 1e-9);
+        assert_eq!(result[1].win, 0.0);
+    }
+
+    #[test]
+    fn test_monte_carlo_preflop() {
+        let options = EquityOptions {
+            samples: 20_000,
+            seed: Some(11),
+            ..EquityOptions::default()
+        };
+        let result = equity(&[holding("AhKh"), holding("QsQc")], &[], &[], &options).unwrap();
+
+        // AKs against QQ is close to a coin flip, slightly favouring the pair.
+        assert!((result[0].equity - 0.46).abs() < 0.015, "{}", result[0]);
+        assert!((result[1].equity - 0.54).abs() < 0.015, "{}", result[1]);
+
+        let again = equity(&[holding("AhKh"), holding("QsQc")], &[], &[], &options).unwrap();
+        assert_eq!(result, again);
+    }
+
+    #[test]
+    fn test_hand_against_range() {
+        let kings: Vec<Holding> = cards("Kc Kd Kh Ks")
+            .into_iter()
+            .tuple_combinations()
+            .collect();
+        let result = equity(
+            &[holding("AcAd"), kings],
+            &cards("2h 7s 9c"),
+            &[],
+            &EquityOptions::default(),
+        )
+        .unwrap();
+
+        assert!(result[0].equity > 0.85);
+        assert!((result[0].equity + result[1].equity - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_errors() {
+        let options = EquityOptions::default();
+        assert_eq!(
+            equity(&[holding("AhKh")], &[], &[], &options),
+            Err(EquityError::NotEnoughPlayers)
+        );
+        assert_eq!(
+            equity(&[holding("AhKh"), holding("AhQh")], &[], &[], &options),
+            Err(EquityError::NoValidMatchup)
+        );
+        assert_eq!(
+            equity(
+                &[holding("AhKh"), holding("QhQs")],
+                &cards("2c 3c 4c"),
+                &cards("2c"),
+                &options
+            ),
+            Err(EquityError::DuplicateCard(cards("2c")[0]))
+        );
+
+        let sampled = EquityOptions {
+            exhaustive_limit: 0,
+            samples: 10,
+            seed: Some(3),
+        };
+        assert_eq!(
+            equity(
+                &[holding("AhKh"), holding("QsQc"), holding("AhQd")],
+                &[],
+                &[],
+                &sampled
+            ),
+            Err(EquityError::NoValidMatchup)
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Table-driven evaluation of five, six and seven cards.
+//!
+//! Cards are folded into thirteen-bit rank masks, one per suit plus one per
+//! multiplicity (ranks seen at least once, twice, three and four times). Two
+//! tables indexed by a rank mask, built once on first use, then answer the
+//! expensive questions:
+//!
+//! - `STRAIGHTS` holds the high card of the best straight in the mask;
+//! - `FLUSHES` holds the full value of the best straight flush or flush that
+//!   can be made from the ranks of a single suit.
+//!
+//! With at most seven cards a flush rules out quads and full houses, so a
+//! flush lookup is final. Otherwise the multiplicity masks give the category
+//! and the kickers directly.
+
+use crate::value::MAX_TIE_BREAKS;
+use crate::{HandRank, HandValue};
+use lazy_static::lazy_static;
+use utils::card::Card;
+
+const MASKS: usize = 1 << 13;
+const WHEEL: u16 = 0b1_0000_0000_1111;
+
+lazy_static! {
+    static ref STRAIGHTS: Vec<u8> = (0..MASKS).map(|mask| straight_high(mask as u16)).collect();
+    static ref FLUSHES: Vec<HandValue> = (0..MASKS).map(|mask| flush_value(mask as u16)).collect();
+}
+
+/// Evaluates five, six or seven distinct cards, returning the value of the
+/// best five-card hand they contain.
+pub fn evaluate(cards: &[Card]) -> HandValue {
+    assert!((5..=7).contains(&cards.len()));
+
+    let mut suits = [0u16; 4];
+    let mut counts = [0u8; 13];
+    for card in cards {
+        let index = card.index();
+        suits[index % 4] |= 1 << (index / 4);
+        counts[index / 4] += 1;
+    }
+
+    if let Some(&suit) = suits.iter().find(|mask| mask.count_ones() >= 5) {
+        return FLUSHES[suit as usize];
+    }
+
+    let mut seen = [0u16; 5];
+    for (rank, &count) in counts.iter().enumerate() {
+        for mask in &mut seen[1..=count as usize] {
+            *mask |= 1 << rank;
+        }
+    }
+    let [_, any, pairs, trips, quads] = seen;
+
+    if quads != 0 {
+        let quad = high_bit(quads);
+        return value(HandRank::FourOfAKind, &[quad], any & !quad, 1);
+    }
+    if trips != 0 {
+        let trip = high_bit(trips);
+        let pair = pairs & !trip;
+        if pair != 0 {
+            return value(HandRank::FullHouse, &[trip, high_bit(pair)], 0, 0);
+        }
+    }
+    let straight = STRAIGHTS[any as usize];
+    if straight != 0 {
+        return value(HandRank::Straight, &[1 << (straight - 2)], 0, 0);
+    }
+    if trips != 0 {
+        let trip = high_bit(trips);
+        return value(HandRank::ThreeOfAKind, &[trip], any & !trip, 2);
+    }
+    if pairs != 0 {
+        let top = high_bit(pairs);
+        let second = pairs & !top;
+        if second != 0 {
+            let second = high_bit(second);
+            return value(HandRank::TwoPair, &[top, second], any & !top & !second, 1);
+        }
+        return value(HandRank::OnePair, &[top], any & !top, 3);
+    }
+    value(HandRank::HighCard, &[], any, 5)
+}
+
+/// Builds a value from group ranks (as single-bit masks) followed by the
+/// `kickers` highest ranks left in `rest`.
+fn value(hand_rank: HandRank, groups: &[u16], mut rest: u16, kickers: usize) -> HandValue {
+    let mut values = [0; MAX_TIE_BREAKS];
+    for (i, &group) in groups.iter().enumerate() {
+        values[i] = group.trailing_zeros() as u8 + 2;
+    }
+    for value in &mut values[groups.len()..groups.len() + kickers] {
+        let bit = high_bit(rest);
+        *value = bit.trailing_zeros() as u8 + 2;
+        rest &= !bit;
+    }
+    HandValue::from_values(hand_rank, values)
+}
+
+fn high_bit(mask: u16) -> u16 {
+    1 << (15 - mask.leading_zeros())
+}
+
+/// Face value of the highest straight in `mask`, or 0 if there is none.
+fn straight_high(mask: u16) -> u8 {
+    for high in (4..13).rev() {
+        let run = 0b1_1111 << (high - 4);
+        if mask & run == run {
+            return high as u8 + 2;
+        }
+    }
+    if mask & WHEEL == WHEEL {
+        return 5;
+    }
+    0
+}
+
+fn flush_value(mask: u16) -> HandValue {
+    if mask.count_ones() < 5 {
+        return value(HandRank::HighCard, &[], 0, 0);
+    }
+    match straight_high(mask) {
+        0 => value(HandRank::Flush, &[], mask, 5),
+        14 => value(HandRank::RoyalFlush, &[1 << 12], 0, 0),
+        high => value(HandRank::StraightFlush, &[1 << (high - 2)], 0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use utils::card::CardList;
+    use utils::deck::Deck;
+    use utils::rank::Rank;
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.parse::<CardList>().unwrap().into_inner()
+    }
+
+    fn rank(value: u8) -> Rank {
+        Rank::from_index(value as usize - 2).unwrap()
+    }
+
+    #[test]
+    fn test_matches_hand_value_on_every_five_card_hand() {
+        let deck: Vec<Card> = Card::all().collect();
+        let mut counts = [0; 10];
+        for hand in deck.into_iter().combinations(5) {
+            let value = evaluate(&hand);
+            assert_eq!(value, HandValue::of(&hand), "{}", CardList::from(hand));
+            counts[value.rank() as usize] += 1;
+        }
+        // The number of five-card hands in each category, from high card to
+        // royal flush.
+        assert_eq!(
+            counts,
+            [1_302_540, 1_098_240, 123_552, 54_912, 10_200, 5_108, 3_744, 624, 36, 4]
+        );
+    }
+
+    fn best_of_five(cards: &[Card]) -> HandValue {
+        cards
+            .iter()
+            .copied()
+            .combinations(5)
+            .map(|five| HandValue::of(&five))
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_matches_best_five_of_six_and_seven() {
+        let mut deck = Deck::from_seed(5);
+        for _ in 0..20_000 {
+            deck.reset();
+            deck.shuffle();
+            let hand = deck.deal(7).unwrap();
+            assert_eq!(evaluate(&hand[..6]), best_of_five(&hand[..6]));
+            assert_eq!(evaluate(&hand), best_of_five(&hand));
+        }
+    }
+
+    #[test]
+    fn test_seven_cards() {
+        assert_eq!(
+            evaluate(&cards("Ah Kh Qh Jh Th 2c 2d")).rank(),
+            HandRank::RoyalFlush
+        );
+        assert_eq!(
+            evaluate(&cards("As 2h 3h 4h 5h 6c Kd")),
+            HandValue::new(HandRank::Straight, &[rank(6)])
+        );
+        assert_eq!(
+            evaluate(&cards("9s 9h 9c 4h 4d 4c Kd")),
+            HandValue::new(HandRank::FullHouse, &[rank(9), rank(4)])
+        );
+        assert_eq!(
+            evaluate(&cards("9s 9h 4h 4d 3c 3d Kd")),
+            HandValue::new(HandRank::TwoPair, &[rank(9), rank(4), rank(13)])
+        );
+    }
+}
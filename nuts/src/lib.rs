@@ -1,61 +1,13 @@
-use itertools::{iproduct, Itertools};
-use rand::Rng;
-use std::collections::HashSet;
-use std::fmt::{Display, Formatter, Write};
-use std::ops::Range;
-
-const RANK_RANGE: Range<usize> = 2..15;
-const SUIT_RANGE: Range<usize> = 0..4;
-
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Card {
-    pub rank: usize,
-    pub suit: usize,
-}
-
-impl Card {
-    fn new(rank: usize, suit: usize) -> Card {
-        assert!(SUIT_RANGE.contains(&suit));
-        assert!(RANK_RANGE.contains(&rank));
-        Card { rank, suit }
-    }
-}
-
-impl Display for Card {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut output = String::new();
-        match self.rank {
-            2 => output.write_str("2"),
-            3 => output.write_str("3"),
-            4 => output.write_str("4"),
-            5 => output.write_str("5"),
-            6 => output.write_str("6"),
-            7 => output.write_str("7"),
-            8 => output.write_str("8"),
-            9 => output.write_str("9"),
-            10 => output.write_str("10"),
-            11 => output.write_str("J"),
-            12 => output.write_str("Q"),
-            13 => output.write_str("K"),
-            14 => output.write_str("A"),
-            _ => output.write_str(""),
-        }
-        .expect("TODO: panic message");
+pub mod equity;
+pub mod evaluator;
+mod value;
 
-        output.write_str(" ").expect("TODO: panic message");
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
 
-        match self.suit {
-            0 => output.write_str("♠"),
-            1 => output.write_str("♥"),
-            2 => output.write_str("♦"),
-            3 => output.write_str("♣"),
-            _ => output.write_str(""),
-        }
-        .expect("TODO: panic message");
-
-        write!(f, "{output}")
-    }
-}
+pub use utils::card::Card;
+pub use utils::deck::Deck;
+pub use value::HandValue;
 
 #[allow(dead_code)]
 pub struct Hand {
@@ -64,33 +16,36 @@ pub struct Hand {
 
 #[allow(dead_code)]
 pub fn find_nuts(community_cards: &[Card]) -> (HandRank, Hand) {
-    let deck: Vec<Card> = iproduct!(SUIT_RANGE, RANK_RANGE)
-        .map(|(suit, rank)| Card { rank, suit })
+    let unseen: Vec<Card> = Card::all()
         .filter(|card| !community_cards.contains(card))
         .collect();
 
-    let unseen = deck.iter().combinations(2);
-    let mut best_hand: Option<Hand> = None;
-    let mut best_rank: Option<HandRank> = None;
+    let mut cards: Vec<Card> = community_cards.to_vec();
+    let mut best_cards: Option<Vec<Card>> = None;
+    let mut best_value: Option<HandValue> = None;
 
-    for combo in unseen {
-        let mut cards: Vec<Card> = community_cards.to_vec();
+    for combo in unseen.iter().combinations(2) {
+        cards.truncate(community_cards.len());
         cards.push(*combo[0]);
         cards.push(*combo[1]);
-        cards.sort();
-
-        for hand in cards.iter().combinations(5) {
-            let hand = Hand::new(hand.into_iter().cloned().collect());
-            let rank = hand.evaluate();
 
-            if best_rank.is_none() || rank > best_rank.unwrap() {
-                best_hand = Some(hand);
-                best_rank = Some(rank);
-            }
+        let value = evaluator::evaluate(&cards);
+        if best_value.is_none() || value > best_value.unwrap() {
+            best_cards = Some(cards.clone());
+            best_value = Some(value);
         }
     }
 
-    (best_rank.unwrap(), best_hand.unwrap())
+    let mut best_cards = best_cards.unwrap();
+    best_cards.sort();
+    let best_hand = best_cards
+        .into_iter()
+        .combinations(5)
+        .map(Hand::new)
+        .max_by_key(|hand| hand.value())
+        .unwrap();
+
+    (best_value.unwrap().rank(), best_hand)
 }
 
 impl Hand {
@@ -100,60 +55,25 @@ impl Hand {
     }
 
     pub fn random(size: usize) -> Hand {
-        let mut ranks_rng = rand::thread_rng();
-        let mut suits_rng = rand::thread_rng();
-        let mut seen: HashSet<Card> = HashSet::new();
-        while seen.len() < size {
-            let random_rank: usize = ranks_rng.gen_range(RANK_RANGE);
-            let random_suit: usize = suits_rng.gen_range(SUIT_RANGE);
-            seen.insert(Card::new(random_rank, random_suit));
-        }
-        Hand {
-            cards: seen.into_iter().collect(),
-        }
+        let mut deck = Deck::new();
+        deck.shuffle();
+        Hand::deal(&mut deck, size)
     }
 
-    fn evaluate(&self) -> HandRank {
-        let mut ranks = [0; 13];
-        let mut suits = [0; 4];
-        let mut is_flush = false;
-        let mut is_straight = false;
-
-        for card in &self.cards {
-            ranks[card.rank - 2] += 1;
-            suits[card.suit] += 1;
-        }
-
-        let max_same_rank = *ranks.iter().max().unwrap();
-        let max_same_suit = *suits.iter().max().unwrap();
-
-        if max_same_suit == 5 {
-            is_flush = true;
-        }
-
-        for window in ranks.windows(5) {
-            if window.iter().all(|&x| x == 1) {
-                is_straight = true;
-                break;
-            }
-        }
+    /// Deals `size` cards from the top of `deck`.
+    pub fn deal(deck: &mut Deck, size: usize) -> Hand {
+        let cards = deck.deal(size).expect("not enough cards left in the deck");
+        Hand { cards }
+    }
 
-        if ranks[0] == 1 && ranks[1..=4].iter().all(|&x| x == 1) && ranks[12] == 1 {
-            is_straight = true;
-        }
+    /// Category and kickers of a five-card hand.
+    pub fn value(&self) -> HandValue {
+        HandValue::of(&self.cards)
+    }
 
-        match (is_straight, is_flush, max_same_rank) {
-            (true, true, _) if self.cards.iter().max().unwrap().rank == 14 => HandRank::RoyalFlush,
-            (true, true, _) => HandRank::StraightFlush,
-            (_, _, 4) => HandRank::FourOfAKind,
-            (_, _, 3) if ranks.iter().filter(|&&x| x == 2).count() > 0 => HandRank::FullHouse,
-            (_, true, _) => HandRank::Flush,
-            (true, _, _) => HandRank::Straight,
-            (_, _, 3) => HandRank::ThreeOfAKind,
-            (_, _, 2) if ranks.iter().filter(|&&x| x == 2).count() > 1 => HandRank::TwoPair,
-            (_, _, 2) => HandRank::OnePair,
-            _ => HandRank::HighCard,
-        }
+    /// Category of a five-card hand.
+    pub fn evaluate(&self) -> HandRank {
+        self.value().rank()
     }
 }
 
@@ -191,16 +111,20 @@ impl Display for HandRank {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::card::CardList;
+    use utils::rank::Rank;
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.parse::<CardList>().unwrap().into_inner()
+    }
+
+    fn card(s: &str) -> Card {
+        s.parse().unwrap()
+    }
 
     #[test]
     fn test_royal_flush() {
-        let community_cards = vec![
-            Card { rank: 10, suit: 0 }, // 10 of clubs
-            Card { rank: 11, suit: 0 }, // Jack of clubs
-            Card { rank: 12, suit: 0 }, // Queen of clubs
-            Card { rank: 13, suit: 0 }, // King of clubs
-            Card { rank: 14, suit: 0 }, // Ace of clubs
-        ];
+        let community_cards = cards("Tc Jc Qc Kc Ac");
 
         let (best_rank, best_hand) = find_nuts(&community_cards);
 
@@ -208,50 +132,58 @@ mod tests {
 
         // Verify the cards in the best hand.
         assert_eq!(best_hand.cards.len(), 5);
-        assert!(best_hand.cards.contains(&Card { rank: 10, suit: 0 }));
-        assert!(best_hand.cards.contains(&Card { rank: 11, suit: 0 }));
-        assert!(best_hand.cards.contains(&Card { rank: 12, suit: 0 }));
-        assert!(best_hand.cards.contains(&Card { rank: 13, suit: 0 }));
-        assert!(best_hand.cards.contains(&Card { rank: 14, suit: 0 }));
+        assert!(best_hand.cards.contains(&card("Tc")));
+        assert!(best_hand.cards.contains(&card("Jc")));
+        assert!(best_hand.cards.contains(&card("Qc")));
+        assert!(best_hand.cards.contains(&card("Kc")));
+        assert!(best_hand.cards.contains(&card("Ac")));
     }
 
     #[test]
     fn test_four_of_a_kind() {
-        let community_cards = vec![
-            Card { rank: 10, suit: 0 }, // 10 of clubs
-            Card { rank: 10, suit: 1 }, // 10 of spades
-            Card { rank: 7, suit: 2 },
-            Card { rank: 2, suit: 0 },
-            Card { rank: 3, suit: 2 },
-        ];
+        let community_cards = cards("Tc Ts 7d 2c 3d");
 
         let (best_rank, best_hand) = find_nuts(&community_cards);
 
         assert_eq!(best_rank, HandRank::FourOfAKind);
 
         assert_eq!(best_hand.cards.len(), 5);
-        assert!(best_hand.cards.contains(&Card { rank: 10, suit: 0 }));
-        assert!(best_hand.cards.contains(&Card { rank: 10, suit: 1 }));
-        assert!(best_hand.cards.contains(&Card { rank: 10, suit: 2 }));
-        assert!(best_hand.cards.contains(&Card { rank: 10, suit: 3 }));
+        assert!(best_hand.cards.contains(&card("Tc")));
+        assert!(best_hand.cards.contains(&card("Td")));
+        assert!(best_hand.cards.contains(&card("Th")));
+        assert!(best_hand.cards.contains(&card("Ts")));
     }
 
     #[test]
     fn test_straight_flush() {
-        let community_cards = vec![
-            Card { rank: 2, suit: 0 }, // 10 of clubs
-            Card { rank: 2, suit: 1 }, // 10 of spades
-            Card { rank: 5, suit: 0 },
-            Card { rank: 6, suit: 0 },
-            Card { rank: 9, suit: 2 },
-        ];
+        let community_cards = cards("2c 2s 5c 6c 9d");
 
         let (best_rank, best_hand) = find_nuts(&community_cards);
 
         assert_eq!(best_rank, HandRank::StraightFlush);
 
         assert_eq!(best_hand.cards.len(), 5);
-        assert!(best_hand.cards.contains(&Card { rank: 3, suit: 0 }));
-        assert!(best_hand.cards.contains(&Card { rank: 4, suit: 0 }));
+        assert!(best_hand.cards.contains(&card("3c")));
+        assert!(best_hand.cards.contains(&card("4c")));
+    }
+
+    #[test]
+    fn test_nuts_uses_kickers() {
+        let (best_rank, best_hand) = find_nuts(&cards("Kh Qh 8h 4c 3d"));
+        assert_eq!(best_rank, HandRank::Flush);
+        assert!(best_hand.cards.contains(&card("Ah")));
+        assert!(best_hand.cards.contains(&card("Jh")));
+
+        let (best_rank, best_hand) = find_nuts(&cards("Ac Ad Ah 7s 2c"));
+        assert_eq!(best_rank, HandRank::FourOfAKind);
+        assert!(best_hand.cards.contains(&card("As")));
+        assert!(best_hand.cards.iter().any(|card| card.rank() == Rank::King));
+    }
+
+    #[test]
+    fn test_wheel_straight_flush_is_not_royal() {
+        let hand = Hand::new(cards("Ah 2h 3h 4h 5h"));
+
+        assert_eq!(hand.evaluate(), HandRank::StraightFlush);
     }
 }
@@ -0,0 +1,175 @@
+use crate::HandRank;
+use std::fmt::{Display, Formatter};
+use utils::card::Card;
+use utils::rank::Rank;
+
+/// The full strength of a five-card hand: its [`HandRank`] category followed
+/// by the ranks that break ties within that category.
+///
+/// The tie-break ranks are, in order of significance:
+///
+/// - straights and straight flushes: the high card, with the wheel (A-2-3-4-5)
+///   counting as five-high;
+/// - four of a kind, full house, three of a kind, two pair and one pair: the
+///   ranks of the groups from largest to smallest, then the kickers;
+/// - flush and high card: all five ranks from highest to lowest.
+///
+/// Everything is packed into a single `u32` (four bits for the category and
+/// four bits per rank), so comparing two values compares the hands.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandValue(u32);
+
+const RANK_BITS: u32 = 4;
+pub(crate) const MAX_TIE_BREAKS: usize = 5;
+
+impl HandValue {
+    /// Builds a value from a category and at most five tie-break ranks, most
+    /// significant first.
+    pub fn new(rank: HandRank, tie_breaks: &[Rank]) -> HandValue {
+        assert!(tie_breaks.len() <= MAX_TIE_BREAKS);
+        let mut values = [0; MAX_TIE_BREAKS];
+        for (value, rank) in values.iter_mut().zip(tie_breaks) {
+            *value = rank.value();
+        }
+        HandValue::from_values(rank, values)
+    }
+
+    /// Like [`HandValue::new`], with the tie-break ranks given as face values
+    /// and padded with zeros.
+    pub(crate) fn from_values(rank: HandRank, tie_breaks: [u8; MAX_TIE_BREAKS]) -> HandValue {
+        let mut value = rank as u32;
+        for tie_break in tie_breaks {
+            value = (value << RANK_BITS) | tie_break as u32;
+        }
+        HandValue(value)
+    }
+
+    /// Evaluates exactly five distinct cards.
+    pub fn of(cards: &[Card]) -> HandValue {
+        assert_eq!(cards.len(), 5);
+
+        let mut counts = [0u8; 13];
+        for card in cards {
+            counts[card.rank().index()] += 1;
+        }
+        let is_flush = cards.iter().all(|card| card.suit() == cards[0].suit());
+
+        // Groups ordered by size, then by rank, e.g. [(3, 9), (2, K)] for 999KK.
+        let mut groups: Vec<(u8, Rank)> = (0..13)
+            .rev()
+            .filter(|&i| counts[i] > 0)
+            .map(|i| (counts[i], Rank::from_index(i).unwrap()))
+            .collect();
+        groups.sort_by(|a, b| b.cmp(a));
+        let ranks: Vec<Rank> = groups.iter().map(|&(_, rank)| rank).collect();
+
+        let straight_high = if groups.len() < 5 {
+            None
+        } else if ranks[0].index() - ranks[4].index() == 4 {
+            Some(ranks[0])
+        } else if ranks == [Rank::Ace, Rank::Five, Rank::Four, Rank::Three, Rank::Two] {
+            Some(Rank::Five)
+        } else {
+            None
+        };
+
+        match (straight_high, is_flush, groups[0].0, groups[1].0) {
+            (Some(Rank::Ace), true, _, _) => HandValue::new(HandRank::RoyalFlush, &[Rank::Ace]),
+            (Some(high), true, _, _) => HandValue::new(HandRank::StraightFlush, &[high]),
+            (_, _, 4, _) => HandValue::new(HandRank::FourOfAKind, &ranks),
+            (_, _, 3, 2) => HandValue::new(HandRank::FullHouse, &ranks),
+            (_, true, _, _) => HandValue::new(HandRank::Flush, &ranks),
+            (Some(high), _, _, _) => HandValue::new(HandRank::Straight, &[high]),
+            (_, _, 3, _) => HandValue::new(HandRank::ThreeOfAKind, &ranks),
+            (_, _, 2, 2) => HandValue::new(HandRank::TwoPair, &ranks),
+            (_, _, 2, _) => HandValue::new(HandRank::OnePair, &ranks),
+            _ => HandValue::new(HandRank::HighCard, &ranks),
+        }
+    }
+
+    pub fn rank(&self) -> HandRank {
+        match self.0 >> (RANK_BITS * MAX_TIE_BREAKS as u32) {
+            0 => HandRank::HighCard,
+            1 => HandRank::OnePair,
+            2 => HandRank::TwoPair,
+            3 => HandRank::ThreeOfAKind,
+            4 => HandRank::Straight,
+            5 => HandRank::Flush,
+            6 => HandRank::FullHouse,
+            7 => HandRank::FourOfAKind,
+            8 => HandRank::StraightFlush,
+            _ => HandRank::RoyalFlush,
+        }
+    }
+
+    /// The tie-break ranks, most significant first.
+    pub fn tie_breaks(&self) -> Vec<Rank> {
+        (0..MAX_TIE_BREAKS as u32)
+            .rev()
+            .map(|i| (self.0 >> (RANK_BITS * i)) & 0xf)
+            .take_while(|&value| value != 0)
+            .map(|value| Rank::from_index(value as usize - 2).unwrap())
+            .collect()
+    }
+}
+
+impl Display for HandValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let tie_breaks: Vec<String> = self.tie_breaks().iter().map(|r| r.to_string()).collect();
+        write!(f, "{} ({})", self.rank(), tie_breaks.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::card::CardList;
+
+    fn value(s: &str) -> HandValue {
+        HandValue::of(&s.parse::<CardList>().unwrap())
+    }
+
+    #[test]
+    fn test_categories() {
+        assert_eq!(value("Ah Kh Qh Jh Th").rank(), HandRank::RoyalFlush);
+        assert_eq!(value("9h Kh Qh Jh Th").rank(), HandRank::StraightFlush);
+        assert_eq!(value("9h 9c 9s 9d Th").rank(), HandRank::FourOfAKind);
+        assert_eq!(value("9h 9c 9s Td Th").rank(), HandRank::FullHouse);
+        assert_eq!(value("2h 9h 4h Jh Th").rank(), HandRank::Flush);
+        assert_eq!(value("Ac 2h 3d 4s 5h").rank(), HandRank::Straight);
+        assert_eq!(value("9h 9c 9s 2d Th").rank(), HandRank::ThreeOfAKind);
+        assert_eq!(value("9h 9c 2s 2d Th").rank(), HandRank::TwoPair);
+        assert_eq!(value("9h 9c 3s 2d Th").rank(), HandRank::OnePair);
+        assert_eq!(value("9h 8c 3s 2d Th").rank(), HandRank::HighCard);
+    }
+
+    #[test]
+    fn test_tie_breaks() {
+        assert_eq!(
+            value("Kh Kc 9s 2d Th").tie_breaks(),
+            vec![Rank::King, Rank::Ten, Rank::Nine, Rank::Two]
+        );
+        assert_eq!(
+            value("2h 2c 9s 9d 2s").tie_breaks(),
+            vec![Rank::Two, Rank::Nine]
+        );
+        assert_eq!(value("Ac 2h 3d 4s 5h").tie_breaks(), vec![Rank::Five]);
+        assert_eq!(value("Ac 2h 3d 4s 5h").to_string(), "Straight (5)");
+    }
+
+    #[test]
+    fn test_ordering() {
+        // Higher flush wins.
+        assert!(value("Ah 9h 4h 3h 2h") > value("Kc Qc Jc Tc 8c"));
+        // Wheel is the lowest straight.
+        assert!(value("Ac 2h 3d 4s 5h") < value("6c 2h 3d 4s 5h"));
+        // Same pair, better kicker.
+        assert!(value("Kh Kc 9s 3d Th") > value("Ks Kd 9c 2h Tc"));
+        // Two pair is decided by the top pair first.
+        assert!(value("Kh Kc 2s 2d 3h") > value("Qh Qc Js Jd Ah"));
+        // Full house is decided by the trips first.
+        assert!(value("3h 3c 3s 2d 2h") > value("2s 2c 2d Ad Ah"));
+        // Identical ranks in different suits tie.
+        assert_eq!(value("Ah Kc 9s 3d Th"), value("Ad Kh 9c 3s Tc"));
+    }
+}
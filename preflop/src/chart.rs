@@ -0,0 +1,380 @@
+use crate::grid::HandGrid;
+use crate::hand_class::HandClass;
+use crate::{Action, Context, Position, Scenario, StackSize, Strategy, TablePosition};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::rank::Rank;
+
+/// A preflop chart: one [`Context`] per line of a text file.
+///
+/// Everything after a `#` is a comment, and blank lines are ignored:
+///
+/// ```text
+/// # Unopened pots, 9-handed
+/// utg,A,K,o,r,a
+/// early,7,7,o,r,d   # deep stacks only
+/// btn,A,5,s,3b,d,vs-open-co
+/// ```
+///
+/// A hand class may be listed only once for the same scenario, position and
+/// stack size, where an `a` (any) stack size overlaps every other size.
+#[derive(Debug, Clone, Default)]
+pub struct Chart {
+    entries: Vec<Context>,
+}
+
+impl Chart {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Chart, ChartError> {
+        fs::read_to_string(path).map_err(ChartError::Io)?.parse()
+    }
+
+    pub fn entries(&self) -> &[Context] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The entry for `class` in `position` with `stack_size`. Entries for the
+    /// exact position win over entries for its group, and entries for the
+    /// exact stack size win over `any`. Facing an open from a position the
+    /// chart has no entry for, the generic "vs-open" entry applies.
+    pub fn get(
+        &self,
+        scenario: Scenario,
+        position: Position,
+        class: HandClass,
+        stack_size: &StackSize,
+    ) -> Option<&Context> {
+        self.find(
+            scenario,
+            |context| context.position == Some(position),
+            class,
+            stack_size,
+        )
+        .or_else(|| self.get_group(scenario, position.into(), class, stack_size))
+    }
+
+    /// The entry for `class` in the position group `table_position`.
+    pub fn get_group(
+        &self,
+        scenario: Scenario,
+        table_position: TablePosition,
+        class: HandClass,
+        stack_size: &StackSize,
+    ) -> Option<&Context> {
+        self.find(
+            scenario,
+            |context| context.position.is_none() && context.table_position == table_position,
+            class,
+            stack_size,
+        )
+    }
+
+    /// Every entry that applies to `position`, whether listed for it or
+    /// for its group.
+    pub fn for_position(&self, position: Position) -> impl Iterator<Item = &Context> {
+        let table_position = TablePosition::from(position);
+        self.entries
+            .iter()
+            .filter(move |context| match context.position {
+                Some(listed) => listed == position,
+                None => context.table_position == table_position,
+            })
+    }
+
+    /// The chart `context` comes from: every entry for the same scenario,
+    /// position and stack size, laid out as a grid.
+    pub fn grid(&self, context: &Context) -> HandGrid<Option<Strategy>> {
+        let mut grid = HandGrid::new();
+        for entry in self
+            .entries
+            .iter()
+            .filter(|entry| same_chart(entry, context))
+        {
+            grid.set_class(entry.hand_class(), Some(entry.strategy().clone()));
+        }
+        grid
+    }
+
+    /// Whether `context` sits on the edge of its range: its strategy is
+    /// mixed, or a neighbouring cell of its grid has a different main
+    /// action. Hands missing from the chart count as folds.
+    pub fn is_boundary(&self, context: &Context) -> bool {
+        if context.strategy().is_mixed() {
+            return true;
+        }
+        let grid = self.grid(context);
+        let (row, column) = HandGrid::<Option<Strategy>>::position(context.hand_class());
+        let step =
+            |rank: Rank, delta: isize| Rank::from_index(rank.index().checked_add_signed(delta)?);
+        let neighbours = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .filter_map(|(dr, dc)| Some((step(row, dr)?, step(column, dc)?)));
+        let action =
+            |cell: &Option<Strategy>| cell.as_ref().map_or(Action::Fold, Strategy::primary);
+        neighbours
+            .map(|(row, column)| action(grid.get(row, column)))
+            .any(|neighbour| neighbour != context.action())
+    }
+
+    fn find<F: Fn(&Context) -> bool>(
+        &self,
+        scenario: Scenario,
+        position: F,
+        class: HandClass,
+        stack_size: &StackSize,
+    ) -> Option<&Context> {
+        let lookup = |scenario: Scenario| {
+            let mut matching = self.entries.iter().filter(|context| {
+                context.scenario == scenario && position(context) && context.hand_class() == class
+            });
+            let exact = matching
+                .clone()
+                .find(|context| context.stack_size == *stack_size);
+            exact.or_else(|| matching.find(|context| context.stack_size == StackSize::Any))
+        };
+        match scenario {
+            Scenario::VsOpen(Some(_)) => {
+                lookup(scenario).or_else(|| lookup(Scenario::VsOpen(None)))
+            }
+            _ => lookup(scenario),
+        }
+    }
+}
+
+/// Parses a whole chart, stopping at the first invalid line.
+impl FromStr for Chart {
+    type Err = ChartError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries: Vec<(usize, Context)> = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line_number = i + 1;
+            let text = line.split('#').next().unwrap_or_default().trim();
+            if text.is_empty() {
+                continue;
+            }
+            let context: Context = text.parse().map_err(|message| ChartError::Parse {
+                line: line_number,
+                message,
+            })?;
+            if let Some((first, _)) = entries.iter().find(|(_, other)| overlaps(other, &context)) {
+                return Err(ChartError::Duplicate {
+                    line: line_number,
+                    first: *first,
+                    hand_class: context.hand_class(),
+                });
+            }
+            entries.push((line_number, context));
+        }
+        Ok(Chart {
+            entries: entries.into_iter().map(|(_, context)| context).collect(),
+        })
+    }
+}
+
+fn same_chart(a: &Context, b: &Context) -> bool {
+    a.scenario == b.scenario
+        && a.table_position == b.table_position
+        && a.position == b.position
+        && a.stack_size == b.stack_size
+}
+
+fn overlaps(a: &Context, b: &Context) -> bool {
+    a.scenario == b.scenario
+        && a.table_position == b.table_position
+        && a.position == b.position
+        && a.hand_class() == b.hand_class()
+        && (a.stack_size == b.stack_size
+            || a.stack_size == StackSize::Any
+            || b.stack_size == StackSize::Any)
+}
+
+#[derive(Debug)]
+pub enum ChartError {
+    Io(io::Error),
+    Parse {
+        line: usize,
+        message: String,
+    },
+    Duplicate {
+        line: usize,
+        first: usize,
+        hand_class: HandClass,
+    },
+}
+
+impl Display for ChartError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChartError::Io(error) => write!(f, "Could not read chart: {}", error),
+            ChartError::Parse { line, message } => write!(f, "Line {}: {}", line, message),
+            ChartError::Duplicate {
+                line,
+                first,
+                hand_class,
+            } => write!(
+                f,
+                "Line {}: {} is already listed for this position and stack size on line {}",
+                line, hand_class, first
+            ),
+        }
+    }
+}
+
+impl Error for ChartError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHART: &str = "\
+# Unopened pots
+early,A,K,o,r,a
+early,7,7,o,f,s   # too short to set mine
+early,7,7,o,r,d
+
+utg,7,7,o,f,d
+btn,A,5,s,3b,d,vs-open-co
+btn,A,5,s,c,d,vs-open
+late,A,5,s,r,d
+";
+
+    fn class(s: &str) -> HandClass {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_lookups() {
+        let chart: Chart = CHART.parse().unwrap();
+        assert_eq!(chart.len(), 7);
+
+        let lookup = |position, hand, stack_size| {
+            chart
+                .get(Scenario::Unopened, position, class(hand), &stack_size)
+                .map(|context| context.action())
+        };
+        assert_eq!(
+            lookup(Position::UnderTheGun1, "77", StackSize::Deep),
+            Some(Action::Raise)
+        );
+        assert_eq!(
+            lookup(Position::UnderTheGun, "77", StackSize::Deep),
+            Some(Action::Fold)
+        );
+        assert_eq!(
+            lookup(Position::UnderTheGun1, "77", StackSize::Short),
+            Some(Action::Fold)
+        );
+        assert_eq!(
+            lookup(Position::UnderTheGun2, "AKo", StackSize::Medium),
+            Some(Action::Raise)
+        );
+        assert_eq!(lookup(Position::Button, "AKo", StackSize::Medium), None);
+        assert_eq!(chart.for_position(Position::UnderTheGun).count(), 4);
+        assert_eq!(chart.for_position(Position::Lojack).count(), 0);
+
+        let vs_open = |opener| {
+            chart
+                .get(
+                    Scenario::VsOpen(Some(opener)),
+                    Position::Button,
+                    class("A5s"),
+                    &StackSize::Deep,
+                )
+                .map(|context| context.action())
+        };
+        assert_eq!(vs_open(Position::Cutoff), Some(Action::ThreeBet));
+        assert_eq!(vs_open(Position::UnderTheGun), Some(Action::Call));
+        assert_eq!(
+            lookup(Position::Button, "A5s", StackSize::Deep),
+            Some(Action::Raise)
+        );
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let chart: Chart = "\
+late,A,A,o,r,d
+late,A,K,s,r,d
+late,A,Q,s,r,d
+late,A,J,s,r:0.5/f:0.5,d
+late,K,K,o,r,d
+late,K,Q,s,r,d
+late,Q,Q,o,r,d
+late,A,A,o,f,s
+"
+        .parse()
+        .unwrap();
+        let entry = |i: usize| &chart.entries()[i];
+
+        let grid = chart.grid(entry(0));
+        assert_eq!(
+            grid.get_class(class("AKs")).as_ref().map(Strategy::primary),
+            Some(Action::Raise)
+        );
+        assert_eq!(*grid.get_class(class("AKo")), None);
+
+        // AKs is surrounded by AA, AQs and KK, which all raise.
+        assert!(!chart.is_boundary(entry(1)));
+        // AA sits next to AKo, which is missing from the chart.
+        assert!(chart.is_boundary(entry(0)));
+        // KQs sits next to KJs, which is missing too.
+        assert!(chart.is_boundary(entry(5)));
+        assert!(chart.is_boundary(entry(3)));
+
+        // "r" and "3b" are the same action when facing an open.
+        let chart: Chart = "\
+btn,A,A,o,3b,d,vs-open
+btn,A,K,s,r,d,vs-open
+btn,A,Q,s,3b,d,vs-open
+btn,K,K,o,r,d,vs-open
+"
+        .parse()
+        .unwrap();
+        assert!(!chart.is_boundary(&chart.entries()[1]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = "early,A,K,o,r,a\nearly,A,X,o,r,a"
+            .parse::<Chart>()
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Line 2: Invalid rank: X");
+
+        let error = "# AK\nearly,A,K,o,r,a\n\nearly,K,A,o,f,d"
+            .parse::<Chart>()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Line 4: AKo is already listed for this position and stack size on line 2"
+        );
+        assert!("early,A,K,o,r,a\nearly,A,K,o,3b,a,vs-open"
+            .parse::<Chart>()
+            .is_ok());
+
+        assert!(matches!(
+            Chart::load("/nonexistent/chart.csv"),
+            Err(ChartError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_load() {
+        let path = std::env::temp_dir().join(format!("preflop-chart-{}.csv", std::process::id()));
+        fs::write(&path, CHART).unwrap();
+        let chart = Chart::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(chart.len(), 7);
+    }
+}
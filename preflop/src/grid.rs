@@ -0,0 +1,254 @@
+use crate::hand_class::HandClass;
+use crate::range::Range;
+use crate::{Action, Strategy, SuitCombination};
+use utils::rank::{Rank, RANKS};
+
+/// The 13×13 preflop chart, one cell per hand class.
+///
+/// Rows and columns run from ace down to deuce. Pairs sit on the diagonal,
+/// suited hands above it (row rank higher than column rank) and offsuit
+/// hands below it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandGrid<T> {
+    cells: Vec<T>,
+}
+
+impl<T: Clone + Default> HandGrid<T> {
+    pub fn new() -> HandGrid<T> {
+        HandGrid {
+            cells: vec![T::default(); RANKS.len() * RANKS.len()],
+        }
+    }
+}
+
+impl<T: Clone + Default> Default for HandGrid<T> {
+    fn default() -> Self {
+        HandGrid::new()
+    }
+}
+
+impl<T> HandGrid<T> {
+    pub fn get(&self, row: Rank, column: Rank) -> &T {
+        &self.cells[offset(row, column)]
+    }
+
+    pub fn set(&mut self, row: Rank, column: Rank, value: T) {
+        self.cells[offset(row, column)] = value;
+    }
+
+    pub fn get_class(&self, class: HandClass) -> &T {
+        let (row, column) = HandGrid::<T>::position(class);
+        self.get(row, column)
+    }
+
+    pub fn set_class(&mut self, class: HandClass, value: T) {
+        let (row, column) = HandGrid::<T>::position(class);
+        self.set(row, column, value);
+    }
+
+    /// The hand class shown at `(row, column)`.
+    pub fn class(row: Rank, column: Rank) -> HandClass {
+        let suit_combination = if row > column {
+            SuitCombination::Suited
+        } else {
+            SuitCombination::Offsuit
+        };
+        HandClass::new(row, column, suit_combination)
+    }
+
+    /// The `(row, column)` cell showing `class`.
+    pub fn position(class: HandClass) -> (Rank, Rank) {
+        if class.is_suited() {
+            (class.high(), class.low())
+        } else {
+            (class.low(), class.high())
+        }
+    }
+
+    /// Renders the chart as thirteen lines of thirteen cells. With `colored`
+    /// set, cells get an ANSI background colour; otherwise each hand class is
+    /// followed by the cell's marker.
+    pub fn render(&self, colored: bool) -> String
+    where
+        T: GridCell,
+    {
+        let mut output = String::new();
+        for row in RANKS {
+            for column in RANKS {
+                let class = HandGrid::<T>::class(row, column).to_string();
+                let cell = self.get(row, column);
+                match (colored, cell.color()) {
+                    (true, Some(color)) => output.push_str(&format!(
+                        "\x1b[48;5;{}m\x1b[38;5;16m {:<3} \x1b[0m",
+                        color, class
+                    )),
+                    (true, None) => output.push_str(&format!(" {:<3} ", class)),
+                    (false, _) => output.push_str(&format!("{:<3} {:<4}", class, cell.marker())),
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl HandGrid<f64> {
+    /// The share of each class's combos in `range`, counted by weight.
+    pub fn from_range(range: &Range) -> HandGrid<f64> {
+        let mut grid = HandGrid::new();
+        for class in HandClass::all() {
+            let combos = class.combos();
+            let weight: f64 = combos.iter().map(|&(a, b)| range.weight(a, b)).sum();
+            grid.set_class(class, weight / combos.len() as f64);
+        }
+        grid
+    }
+}
+
+fn offset(row: Rank, column: Rank) -> usize {
+    // RANKS runs from the ace down, so row 0 is the ace.
+    (12 - row.index()) * RANKS.len() + (12 - column.index())
+}
+
+/// How a value is drawn in a [`HandGrid`].
+pub trait GridCell {
+    /// Short text printed after the hand class when colours are off.
+    fn marker(&self) -> String;
+
+    /// ANSI 256-colour background, or `None` to leave the cell blank.
+    fn color(&self) -> Option<u8>;
+}
+
+/// Frequencies between 0 and 1, shaded from light to dark green.
+impl GridCell for f64 {
+    fn marker(&self) -> String {
+        if *self <= 0.0 {
+            ".".to_string()
+        } else {
+            format!("{:.0}%", self * 100.0)
+        }
+    }
+
+    fn color(&self) -> Option<u8> {
+        match *self {
+            f if f <= 0.0 => None,
+            f if f < 0.25 => Some(194),
+            f if f < 0.5 => Some(157),
+            f if f < 0.75 => Some(120),
+            f if f < 1.0 => Some(77),
+            _ => Some(34),
+        }
+    }
+}
+
+impl GridCell for Action {
+    fn marker(&self) -> String {
+        match self {
+            Action::Raise => "R",
+            Action::ThreeBet => "3B",
+            Action::FourBet => "4B",
+            Action::Jam => "J",
+            Action::Call => "C",
+            Action::Fold => ".",
+        }
+        .to_string()
+    }
+
+    fn color(&self) -> Option<u8> {
+        match self {
+            Action::Raise => Some(167),
+            Action::ThreeBet => Some(203),
+            Action::FourBet => Some(134),
+            Action::Jam => Some(160),
+            Action::Call => Some(78),
+            Action::Fold => None,
+        }
+    }
+}
+
+/// Mixed strategies show their most frequent action and how often it is
+/// taken, as in "R60".
+impl GridCell for Strategy {
+    fn marker(&self) -> String {
+        let marker = self.primary().marker();
+        if self.is_mixed() {
+            format!("{}{:.0}", marker, self.frequency(self.primary()) * 100.0)
+        } else {
+            marker
+        }
+    }
+
+    fn color(&self) -> Option<u8> {
+        self.primary().color()
+    }
+}
+
+impl<T: GridCell> GridCell for Option<T> {
+    fn marker(&self) -> String {
+        match self {
+            Some(value) => value.marker(),
+            None => String::new(),
+        }
+    }
+
+    fn color(&self) -> Option<u8> {
+        self.as_ref().and_then(GridCell::color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(
+            HandGrid::<f64>::class(Rank::Ace, Rank::King),
+            "AKs".parse().unwrap()
+        );
+        assert_eq!(
+            HandGrid::<f64>::class(Rank::King, Rank::Ace),
+            "AKo".parse().unwrap()
+        );
+        assert_eq!(
+            HandGrid::<f64>::class(Rank::Seven, Rank::Seven),
+            "77".parse().unwrap()
+        );
+        for class in HandClass::all() {
+            let (row, column) = HandGrid::<f64>::position(class);
+            assert_eq!(HandGrid::<f64>::class(row, column), class);
+        }
+    }
+
+    #[test]
+    fn test_from_range() {
+        let range: Range = "QQ+, AKs, AKo:0.5, AhQh".parse().unwrap();
+        let grid = HandGrid::from_range(&range);
+        assert_eq!(*grid.get(Rank::Ace, Rank::Ace), 1.0);
+        assert_eq!(*grid.get(Rank::Ace, Rank::King), 1.0);
+        assert_eq!(*grid.get(Rank::King, Rank::Ace), 0.5);
+        assert_eq!(*grid.get(Rank::Ace, Rank::Queen), 0.25);
+        assert_eq!(*grid.get(Rank::Jack, Rank::Jack), 0.0);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut grid: HandGrid<Option<Action>> = HandGrid::new();
+        grid.set_class("AA".parse().unwrap(), Some(Action::Raise));
+        grid.set_class("AKo".parse().unwrap(), Some(Action::Call));
+
+        let plain = grid.render(false);
+        let lines: Vec<&str> = plain.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert!(lines[0].starts_with("AA  R   AKs     AQs"));
+        assert!(lines[1].starts_with("AKo C   KK      KQs"));
+        assert!(!plain.contains('\x1b'));
+
+        let mixed: Strategy = "r:0.6/c:0.4".parse().unwrap();
+        assert_eq!(mixed.marker(), "R60");
+
+        let colored = grid.render(true);
+        assert!(colored.contains("\x1b[48;5;167m"));
+        assert!(colored.contains("\x1b[48;5;78m"));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::SuitCombination;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use utils::card::Card;
+use utils::rank::{Rank, RANKS};
+use utils::suit::SUITS;
+
+/// One of the 169 starting-hand classes, such as "AKs", "T9o" or "QQ".
+///
+/// The higher rank always comes first. Pocket pairs are stored as offsuit.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct HandClass {
+    high: Rank,
+    low: Rank,
+    suit_combination: SuitCombination,
+}
+
+impl HandClass {
+    pub fn new(rank_1: Rank, rank_2: Rank, suit_combination: SuitCombination) -> HandClass {
+        let suit_combination = if rank_1 == rank_2 {
+            SuitCombination::Offsuit
+        } else {
+            suit_combination
+        };
+        HandClass {
+            high: rank_1.max(rank_2),
+            low: rank_1.min(rank_2),
+            suit_combination,
+        }
+    }
+
+    pub fn pair(rank: Rank) -> HandClass {
+        HandClass::new(rank, rank, SuitCombination::Offsuit)
+    }
+
+    /// Every hand class, pairs first, from AA down to 32o.
+    pub fn all() -> Vec<HandClass> {
+        let mut classes: Vec<HandClass> = RANKS.iter().map(|&rank| HandClass::pair(rank)).collect();
+        for (i, &high) in RANKS.iter().enumerate() {
+            for &low in &RANKS[i + 1..] {
+                classes.push(HandClass::new(high, low, SuitCombination::Suited));
+                classes.push(HandClass::new(high, low, SuitCombination::Offsuit));
+            }
+        }
+        classes
+    }
+
+    /// The class of two concrete hole cards.
+    pub fn of(card_1: Card, card_2: Card) -> HandClass {
+        let suit_combination = if card_1.suit() == card_2.suit() {
+            SuitCombination::Suited
+        } else {
+            SuitCombination::Offsuit
+        };
+        HandClass::new(card_1.rank(), card_2.rank(), suit_combination)
+    }
+
+    pub fn high(&self) -> Rank {
+        self.high
+    }
+
+    pub fn low(&self) -> Rank {
+        self.low
+    }
+
+    pub fn suit_combination(&self) -> SuitCombination {
+        self.suit_combination
+    }
+
+    pub fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    pub fn is_suited(&self) -> bool {
+        self.suit_combination == SuitCombination::Suited
+    }
+
+    /// Every concrete pair of hole cards in this class, higher card first.
+    pub fn combos(&self) -> Vec<(Card, Card)> {
+        let mut combos = Vec::new();
+        for (i, suit_1) in SUITS.iter().enumerate() {
+            for (j, suit_2) in SUITS.iter().enumerate() {
+                let valid = match self.suit_combination {
+                    _ if self.is_pair() => i > j,
+                    SuitCombination::Suited => i == j,
+                    SuitCombination::Offsuit => i != j,
+                };
+                if valid {
+                    combos.push((Card::new(self.high, *suit_1), Card::new(self.low, *suit_2)));
+                }
+            }
+        }
+        combos
+    }
+}
+
+/// Parses "AKs", "AKo" and "QQ". The ranks may come in either order.
+impl FromStr for HandClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.trim().chars().collect();
+        let (rank_1, rank_2, suit_combination) = match chars.as_slice() {
+            [r1, r2] if r1 == r2 => (r1, r2, SuitCombination::Offsuit),
+            [r1, r2, suit] if r1 != r2 => (r1, r2, suit.to_string().parse()?),
+            _ => return Err(format!("Invalid hand class: {}", s)),
+        };
+        Ok(HandClass::new(
+            rank_1.to_string().parse()?,
+            rank_2.to_string().parse()?,
+            suit_combination,
+        ))
+    }
+}
+
+impl Display for HandClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_pair() {
+            write!(f, "{}{}", self.high, self.low)
+        } else if self.is_suited() {
+            write!(f, "{}{}s", self.high, self.low)
+        } else {
+            write!(f, "{}{}o", self.high, self.low)
+        }
+    }
+}
@@ -1,12 +1,40 @@
+pub mod chart;
+pub mod grid;
+pub mod hand_class;
+pub mod push_fold;
+pub mod range;
+pub mod scenario;
+pub mod strategy;
+
+pub use fundamentals::position::Position;
+use hand_class::HandClass;
+pub use scenario::Scenario;
 use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
+pub use strategy::{Grade, Strategy};
+use utils::card::Card;
 use utils::rank::Rank;
 
-#[derive(Debug, Eq, PartialEq)]
-enum TablePosition {
+/// The coarse position groups charts are written for.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TablePosition {
     Early,
     Middle,
     Late,
+    Blinds,
+}
+
+impl From<Position> for TablePosition {
+    fn from(position: Position) -> Self {
+        match position {
+            Position::UnderTheGun | Position::UnderTheGun1 | Position::UnderTheGun2 => {
+                TablePosition::Early
+            }
+            Position::Lojack | Position::Hijack => TablePosition::Middle,
+            Position::Cutoff | Position::Button => TablePosition::Late,
+            Position::SmallBlind | Position::BigBlind => TablePosition::Blinds,
+        }
+    }
 }
 
 impl FromStr for TablePosition {
@@ -17,6 +45,7 @@ impl FromStr for TablePosition {
             "early" => Ok(TablePosition::Early),
             "middle" => Ok(TablePosition::Middle),
             "late" => Ok(TablePosition::Late),
+            "blinds" => Ok(TablePosition::Blinds),
             _ => Err(format!("Invalid table position: {}", s)),
         }
     }
@@ -28,13 +57,20 @@ impl Display for TablePosition {
             TablePosition::Early => write!(f, "early"),
             TablePosition::Middle => write!(f, "middle"),
             TablePosition::Late => write!(f, "late"),
+            TablePosition::Blinds => write!(f, "blinds"),
         }
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-enum Action {
-    Raise(StackSize),
+/// A preflop response. Raise sizes come from `fundamentals::pot::Preset`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Action {
+    /// Open, or isolate limpers.
+    Raise,
+    ThreeBet,
+    FourBet,
+    /// Move all-in.
+    Jam,
     Call,
     Fold,
 }
@@ -44,7 +80,10 @@ impl FromStr for Action {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "r" => Ok(Action::Raise(StackSize::Short)),
+            "r" => Ok(Action::Raise),
+            "3" | "3b" => Ok(Action::ThreeBet),
+            "4" | "4b" => Ok(Action::FourBet),
+            "j" => Ok(Action::Jam),
             "f" => Ok(Action::Fold),
             "c" => Ok(Action::Call),
             _ => Err(format!("Invalid action: {}", s)),
@@ -52,6 +91,19 @@ impl FromStr for Action {
     }
 }
 
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Raise => write!(f, "raise"),
+            Action::ThreeBet => write!(f, "3-bet"),
+            Action::FourBet => write!(f, "4-bet"),
+            Action::Jam => write!(f, "jam"),
+            Action::Call => write!(f, "call"),
+            Action::Fold => write!(f, "fold"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum StackSize {
     Short,
@@ -60,6 +112,41 @@ pub enum StackSize {
     Any,
 }
 
+/// Upper bounds, in big blinds, of the short and medium stack buckets.
+/// Anything deeper than `medium` is deep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StackBuckets {
+    pub short: f64,
+    pub medium: f64,
+}
+
+impl Default for StackBuckets {
+    fn default() -> Self {
+        StackBuckets {
+            short: 25.0,
+            medium: 70.0,
+        }
+    }
+}
+
+impl StackSize {
+    /// The bucket a stack of `big_blinds` falls in.
+    pub fn from_big_blinds(big_blinds: f64, buckets: &StackBuckets) -> StackSize {
+        if big_blinds <= buckets.short {
+            StackSize::Short
+        } else if big_blinds <= buckets.medium {
+            StackSize::Medium
+        } else {
+            StackSize::Deep
+        }
+    }
+
+    /// The bucket a stack of `chips` falls in with blinds of `big_blind`.
+    pub fn from_chips(chips: u32, big_blind: u32, buckets: &StackBuckets) -> StackSize {
+        StackSize::from_big_blinds(chips as f64 / big_blind as f64, buckets)
+    }
+}
+
 impl FromStr for StackSize {
     type Err = String;
 
@@ -85,8 +172,8 @@ impl Display for StackSize {
     }
 }
 
-#[derive(Debug)]
-enum SuitCombination {
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum SuitCombination {
     Suited,
     Offsuit,
 }
@@ -112,31 +199,73 @@ impl Display for SuitCombination {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Context {
     table_position: TablePosition,
+    position: Option<Position>,
+    scenario: Scenario,
     card_1: Rank,
     card_2: Rank,
     suit_combination: SuitCombination,
-    action: Action,
+    strategy: Strategy,
     stack_size: StackSize,
 }
 
 impl Context {
+    /// Whether `action` is an acceptable answer: one the chart takes at
+    /// least [`strategy::MIN_FREQUENCY`] of the time.
     pub fn assess(&self, action: &str) -> bool {
-        let action: Action = match action.trim().parse() {
-            Ok(action) => action,
-            Err(_) => return false,
+        matches!(self.grade(action), Some(grade) if grade.is_correct())
+    }
+
+    /// Grades `action` against the chart's frequencies, or `None` if it is
+    /// not a valid action. A plain "r" stands for whatever raise the
+    /// scenario allows, so it also answers a 3-bet or a 4-bet.
+    pub fn grade(&self, action: &str) -> Option<Grade> {
+        let action = match action.trim().parse().ok()? {
+            Action::Raise => self.scenario.raise(),
+            action => action,
         };
+        Some(self.strategy.grade(action))
+    }
 
-        self.action == action
+    pub fn scenario(&self) -> Scenario {
+        self.scenario
     }
-    pub fn get_expected_action(&self) -> &str {
-        match self.action {
-            Action::Raise(_) => "raise",
-            Action::Call => "call",
-            Action::Fold => "fold",
-        }
+
+    pub fn table_position(&self) -> TablePosition {
+        self.table_position
+    }
+
+    /// The exact position, if the chart gives one rather than a group.
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+
+    pub fn stack_size(&self) -> &StackSize {
+        &self.stack_size
+    }
+
+    /// The chart's most frequent action.
+    pub fn action(&self) -> Action {
+        self.strategy.primary()
+    }
+
+    pub fn strategy(&self) -> &Strategy {
+        &self.strategy
+    }
+
+    pub fn hand_class(&self) -> HandClass {
+        HandClass::new(self.card_1, self.card_2, self.suit_combination)
+    }
+
+    /// Every concrete pair of hole cards described by this context.
+    pub fn hole_cards(&self) -> Vec<(Card, Card)> {
+        self.hand_class().combos()
+    }
+
+    pub fn get_expected_action(&self) -> String {
+        self.strategy.to_string()
     }
 }
 
@@ -144,47 +273,45 @@ impl FromStr for Context {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // An optional seventh field gives the scenario; without it the pot
+        // is unopened.
         let parts: Vec<&str> = s.split(',').collect();
-        if parts.len() != 6 {
+        if parts.len() != 6 && parts.len() != 7 {
             return Err("Invalid string format".to_string());
         }
 
-        let table_position: TablePosition = match parts[0].trim().parse() {
-            Ok(position) => position,
-            Err(e) => return Err(e),
+        // The position is either a group such as "early" or an exact
+        // position such as "utg" or "btn".
+        let (table_position, position) = match parts[0].trim().parse::<Position>() {
+            Ok(position) => (position.into(), Some(position)),
+            Err(_) => (parts[0].trim().parse()?, None),
         };
 
-        let card_1: Rank = match parts[1].trim().parse() {
-            Ok(rank) => rank,
-            Err(e) => return Err(e),
-        };
+        let card_1: Rank = parts[1].trim().parse()?;
 
-        let card_2: Rank = match parts[2].trim().parse() {
-            Ok(rank) => rank,
-            Err(e) => return Err(e),
-        };
+        let card_2: Rank = parts[2].trim().parse()?;
 
-        let suit_combination: SuitCombination = match parts[3].trim().parse() {
-            Ok(suit) => suit,
-            Err(e) => return Err(e),
-        };
+        let suit_combination: SuitCombination = parts[3].trim().parse()?;
 
-        let action: Action = match parts[4].trim().parse() {
-            Ok(action) => action,
-            Err(e) => return Err(e),
-        };
+        let stack_size: StackSize = parts[5].trim().parse()?;
 
-        let stack_size: StackSize = match parts[5].trim().parse() {
-            Ok(size) => size,
-            Err(e) => return Err(e),
+        let scenario: Scenario = match parts.get(6) {
+            Some(scenario) => scenario.parse()?,
+            None => Scenario::Unopened,
         };
 
+        // A plain "r" is the raise the scenario allows, so a vs-open row can
+        // say "r" or "3b" and be graded the same.
+        let strategy = parts[4].trim().parse::<Strategy>()?.in_scenario(scenario)?;
+
         Ok(Context {
             table_position,
+            position,
+            scenario,
             card_1,
             card_2,
             suit_combination,
-            action,
+            strategy,
             stack_size,
         })
     }
@@ -192,10 +319,135 @@ impl FromStr for Context {
 
 impl Display for Context {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{}", position)?,
+            None => write!(f, "{} pos", self.table_position)?,
+        }
+        if self.scenario != Scenario::Unopened {
+            write!(f, " {}", self.scenario)?;
+        }
         write!(
             f,
-            "{} pos, {}{} {}, {} stack",
-            self.table_position, self.card_1, self.card_2, self.suit_combination, self.stack_size
+            ", {}{} {}, {} stack",
+            self.card_1, self.card_2, self.suit_combination, self.stack_size
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_buckets() {
+        let buckets = StackBuckets::default();
+        assert_eq!(
+            StackSize::from_chips(14000, 200, &buckets),
+            StackSize::Medium
+        );
+        assert_eq!(StackSize::from_chips(60000, 400, &buckets), StackSize::Deep);
+        assert_eq!(StackSize::from_chips(4000, 200, &buckets), StackSize::Short);
+
+        let buckets = StackBuckets {
+            short: 15.0,
+            medium: 40.0,
+        };
+        assert_eq!(
+            StackSize::from_big_blinds(20.0, &buckets),
+            StackSize::Medium
+        );
+        assert_eq!(StackSize::from_big_blinds(40.5, &buckets), StackSize::Deep);
+    }
+
+    #[test]
+    fn test_positions() {
+        let grouped: Context = "middle,K,Q,s,r,m".parse().unwrap();
+        assert_eq!(grouped.table_position(), TablePosition::Middle);
+        assert_eq!(grouped.position(), None);
+        assert_eq!(grouped.to_string(), "middle pos, KQ suited, medium stack");
+
+        let exact: Context = "co,K,Q,s,r,m".parse().unwrap();
+        assert_eq!(exact.table_position(), TablePosition::Late);
+        assert_eq!(exact.position(), Some(Position::Cutoff));
+        assert_eq!(exact.to_string(), "CO, KQ suited, medium stack");
+
+        assert!("utg+1,A,T,o,f,d".parse::<Context>().is_ok());
+        assert!("dealer,A,T,o,f,d".parse::<Context>().is_err());
+    }
+
+    #[test]
+    fn test_scenarios() {
+        let open: Context = "late,A,5,s,r,d".parse().unwrap();
+        assert_eq!(open.scenario(), Scenario::Unopened);
+        assert!(open.assess("r"));
+
+        let three_bet: Context = "btn,A,5,s,3b,d,vs-open-co".parse().unwrap();
+        assert_eq!(
+            three_bet.to_string(),
+            "BTN vs CO open, A5 suited, deep stack"
+        );
+        assert!(three_bet.assess("r"));
+        assert!(three_bet.assess("3"));
+        assert!(!three_bet.assess("c"));
+
+        let four_bet: Context = "co,K,K,o,4b,d,vs-3bet".parse().unwrap();
+        assert!(four_bet.assess("r"));
+        assert_eq!(four_bet.get_expected_action(), "4-bet");
+
+        let jam: Context = "sb,A,J,o,j,s,squeeze".parse().unwrap();
+        assert!(jam.assess("j"));
+        assert!(!jam.assess("r"));
+
+        assert!("bb,7,6,s,c,m,vs-limpers".parse::<Context>().is_err());
+        assert!("late,A,5,s,3b,d".parse::<Context>().is_err());
+        assert!("bb,7,6,s,3b,m,vs-limp".parse::<Context>().is_err());
+        assert!("co,K,K,o,4b,d,vs-open".parse::<Context>().is_err());
+    }
+
+    #[test]
+    fn test_plain_raise_in_chart() {
+        let three_bet: Context = "btn,A,5,s,r,d,vs-open".parse().unwrap();
+        assert_eq!(three_bet.action(), Action::ThreeBet);
+        assert!(three_bet.assess("r"));
+        assert!(three_bet.assess("3"));
+        assert_eq!(three_bet.get_expected_action(), "3-bet");
+
+        let four_bet: Context = "co,K,K,o,r,d,vs-3bet".parse().unwrap();
+        assert!(four_bet.assess("r"));
+        assert!(four_bet.assess("4"));
+        assert!(!four_bet.assess("3"));
+
+        let isolate: Context = "btn,A,J,o,r,d,vs-limp".parse().unwrap();
+        assert_eq!(isolate.action(), Action::Raise);
+        assert!(isolate.assess("r"));
+    }
+
+    #[test]
+    fn test_mixed_strategies() {
+        let mixed: Context = "btn,A,5,s,3b:0.6/c:0.4,d,vs-open".parse().unwrap();
+        assert_eq!(mixed.action(), Action::ThreeBet);
+        assert!(mixed.assess("r"));
+        assert!(mixed.assess("c"));
+        assert!(!mixed.assess("f"));
+        assert_eq!(mixed.grade("f").unwrap().score, 0.0);
+        assert!((mixed.grade("c").unwrap().score - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(mixed.grade("x"), None);
+        assert_eq!(mixed.get_expected_action(), "3-bet 60% / call 40%");
+    }
+
+    #[test]
+    fn test_hole_cards() {
+        let suited: Context = "late,A,K,s,r,d".parse().unwrap();
+        assert_eq!(suited.hole_cards().len(), 4);
+        assert!(suited
+            .hole_cards()
+            .iter()
+            .all(|(a, b)| a.suit() == b.suit() && a.rank() == Rank::Ace && b.rank() == Rank::King));
+
+        let offsuit: Context = "late,A,K,o,r,d".parse().unwrap();
+        assert_eq!(offsuit.hole_cards().len(), 12);
+
+        let pair: Context = "early,Q,Q,o,r,d".parse().unwrap();
+        assert_eq!(pair.hole_cards().len(), 6);
+    }
+}
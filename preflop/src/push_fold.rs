@@ -0,0 +1,235 @@
+//! Jam-or-fold equilibria between the small blind and the big blind.
+//!
+//! The small blind either moves all-in or folds, and the big blind either
+//! calls or folds. The solver runs fictitious play over the 169 hand
+//! classes: each side repeatedly best-responds to the other's average
+//! strategy, and the averages converge to a Nash equilibrium.
+
+use crate::grid::HandGrid;
+use crate::hand_class::HandClass;
+use nuts::evaluator::evaluate;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use utils::card::{Card, DECK_SIZE};
+
+const CLASSES: usize = 169;
+
+/// All-in equity of every hand class against every other, with ties
+/// counted as half.
+#[derive(Debug, Clone)]
+pub struct EquityTable {
+    classes: Vec<HandClass>,
+    values: Vec<f64>,
+    /// How many combos of each class are left by a combo of another.
+    blockers: Vec<f64>,
+}
+
+impl EquityTable {
+    /// Builds the table from `equity(hero, villain)`, which should return
+    /// the hero's all-in equity.
+    pub fn from_fn<F: FnMut(HandClass, HandClass) -> f64>(mut equity: F) -> EquityTable {
+        let classes = HandClass::all();
+        let mut values = vec![0.5; CLASSES * CLASSES];
+        for i in 0..CLASSES {
+            for j in i + 1..CLASSES {
+                let value = equity(classes[i], classes[j]);
+                values[i * CLASSES + j] = value;
+                values[j * CLASSES + i] = 1.0 - value;
+            }
+        }
+        let blockers = blockers(&classes);
+        EquityTable {
+            classes,
+            values,
+            blockers,
+        }
+    }
+
+    /// Estimates every matchup by dealing `samples` random boards to random
+    /// combos of the two classes.
+    pub fn monte_carlo(samples: usize, seed: u64) -> EquityTable {
+        let mut rng = StdRng::seed_from_u64(seed);
+        EquityTable::from_fn(|hero, villain| {
+            let (hero, villain) = (hero.combos(), villain.combos());
+            let mut won = 0.0;
+            for _ in 0..samples {
+                won += showdown(&hero, &villain, &mut rng);
+            }
+            won / samples as f64
+        })
+    }
+
+    pub fn get(&self, hero: HandClass, villain: HandClass) -> f64 {
+        self.values[self.index(hero) * CLASSES + self.index(villain)]
+    }
+
+    fn index(&self, class: HandClass) -> usize {
+        self.classes.iter().position(|&c| c == class).unwrap()
+    }
+}
+
+fn blockers(classes: &[HandClass]) -> Vec<f64> {
+    let mut blockers = vec![0.0; CLASSES * CLASSES];
+    for (i, hero) in classes.iter().enumerate() {
+        let hero = hero.combos();
+        for (j, villain) in classes.iter().enumerate() {
+            let villain = villain.combos();
+            let disjoint = hero
+                .iter()
+                .flat_map(|&(a, b)| villain.iter().map(move |&(c, d)| (a, b, c, d)))
+                .filter(|&(a, b, c, d)| a != c && a != d && b != c && b != d)
+                .count();
+            blockers[i * CLASSES + j] = disjoint as f64 / hero.len() as f64;
+        }
+    }
+    blockers
+}
+
+/// Plays out one random board between random combos of two classes.
+fn showdown(hero: &[(Card, Card)], villain: &[(Card, Card)], rng: &mut StdRng) -> f64 {
+    let mut cards = Vec::with_capacity(9);
+    loop {
+        let (a, b) = hero[rng.gen_range(0..hero.len())];
+        let (c, d) = villain[rng.gen_range(0..villain.len())];
+        if a != c && a != d && b != c && b != d {
+            cards.extend([a, b, c, d]);
+            break;
+        }
+    }
+    while cards.len() < 9 {
+        let card = Card::from_index(rng.gen_range(0..DECK_SIZE)).unwrap();
+        if !cards.contains(&card) {
+            cards.push(card);
+        }
+    }
+    let board = &cards[4..];
+    let hero = evaluate(&[&cards[..2], board].concat());
+    let villain = evaluate(&[&cards[2..4], board].concat());
+    match hero.cmp(&villain) {
+        std::cmp::Ordering::Greater => 1.0,
+        std::cmp::Ordering::Equal => 0.5,
+        std::cmp::Ordering::Less => 0.0,
+    }
+}
+
+/// A jam-or-fold spot. Amounts are in big blinds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PushFold {
+    /// Effective stack after antes, blinds included.
+    pub stack: f64,
+    /// Antes and any other dead money in the pot besides the blinds.
+    pub dead: f64,
+}
+
+impl PushFold {
+    pub fn heads_up(stack: f64) -> PushFold {
+        PushFold { stack, dead: 0.0 }
+    }
+
+    /// The small blind against the big blind at a table of `players` who
+    /// each ante `ante`.
+    pub fn with_antes(stack: f64, ante: f64, players: usize) -> PushFold {
+        PushFold {
+            stack,
+            dead: ante * players as f64,
+        }
+    }
+
+    /// Solves the spot with `iterations` rounds of fictitious play.
+    pub fn solve(&self, equities: &EquityTable, iterations: usize) -> PushFoldChart {
+        let classes = &equities.classes;
+        let blockers = &equities.blockers;
+        let pot = 2.0 * self.stack + self.dead;
+        // Values are measured from the stack before the blinds: folding the
+        // small blind is worth -0.5, folding the big blind -1.
+        let called = |equity: f64| equity * pot - self.stack;
+        let mut push = vec![1.0; CLASSES];
+        let mut call = vec![1.0; CLASSES];
+        for iteration in 1..=iterations {
+            let step = 1.0 / (iteration + 1) as f64;
+            for (i, frequency) in push.iter_mut().enumerate() {
+                let (weight, equity) = against(i, &call, blockers, &equities.values);
+                let total: f64 = blockers[i * CLASSES..(i + 1) * CLASSES].iter().sum();
+                let calls = weight / total;
+                let value = (1.0 - calls) * (1.0 + self.dead) + calls * called(equity);
+                let best = if value > -0.5 { 1.0 } else { 0.0 };
+                *frequency += (best - *frequency) * step;
+            }
+            for (i, frequency) in call.iter_mut().enumerate() {
+                let (_, equity) = against(i, &push, blockers, &equities.values);
+                let best = if called(equity) > -1.0 { 1.0 } else { 0.0 };
+                *frequency += (best - *frequency) * step;
+            }
+        }
+
+        let mut chart = PushFoldChart {
+            push: HandGrid::new(),
+            call: HandGrid::new(),
+        };
+        for (i, &class) in classes.iter().enumerate() {
+            chart.push.set_class(class, push[i]);
+            chart.call.set_class(class, call[i]);
+        }
+        chart
+    }
+}
+
+/// The combo weight of `range` left by hand class `i`, and class `i`'s
+/// equity against it.
+fn against(i: usize, range: &[f64], blockers: &[f64], equities: &[f64]) -> (f64, f64) {
+    let row = i * CLASSES..(i + 1) * CLASSES;
+    let mut weight = 0.0;
+    let mut won = 0.0;
+    for ((frequency, combos), equity) in
+        range.iter().zip(&blockers[row.clone()]).zip(&equities[row])
+    {
+        weight += frequency * combos;
+        won += frequency * combos * equity;
+    }
+    if weight == 0.0 {
+        (0.0, 0.0)
+    } else {
+        (weight, won / weight)
+    }
+}
+
+/// How often each hand class jams from the small blind and calls from the
+/// big blind at equilibrium.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushFoldChart {
+    pub push: HandGrid<f64>,
+    pub call: HandGrid<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_fold() {
+        let equities = EquityTable::monte_carlo(30, 7);
+        let class = |s: &str| -> HandClass { s.parse().unwrap() };
+        assert!(equities.get(class("AA"), class("72o")) > 0.75);
+        assert!((equities.get(class("AKo"), class("22")) - 0.47).abs() < 0.15);
+
+        let chart = PushFold::heads_up(10.0).solve(&equities, 300);
+        assert!(*chart.push.get_class(class("AA")) > 0.99);
+        assert!(*chart.push.get_class(class("K9o")) > 0.99);
+        assert!(*chart.call.get_class(class("AA")) > 0.99);
+        assert!(*chart.call.get_class(class("72o")) < 0.01);
+
+        // Shorter stacks jam wider, and antes make jamming wider still.
+        let jams = |chart: &PushFoldChart| {
+            HandClass::all()
+                .iter()
+                .map(|&c| *chart.push.get_class(c))
+                .sum::<f64>()
+        };
+        let deep = jams(&PushFold::heads_up(20.0).solve(&equities, 300));
+        let short = jams(&PushFold::heads_up(3.0).solve(&equities, 300));
+        let antes = jams(&PushFold::with_antes(20.0, 0.125, 8).solve(&equities, 300));
+        assert!(short > jams(&chart));
+        assert!(jams(&chart) > deep);
+        assert!(antes > deep);
+    }
+}
@@ -0,0 +1,385 @@
+use crate::hand_class::HandClass;
+use crate::SuitCombination;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use utils::card::{Card, CardList};
+use utils::rank::{Rank, RANKS};
+
+/// Two hole cards, higher card first.
+pub type Combo = (Card, Card);
+
+/// A weighted set of hole-card combos.
+///
+/// Ranges are written in the usual notation, with entries separated by
+/// commas or spaces:
+///
+/// - `QQ`, `AKs`, `KJo`, `T9` (both suited and offsuit);
+/// - `TT+` (tens or better), `AQs+` (AQs and AKs);
+/// - `TT-77`, `A5s-A2s`;
+/// - `AhKh` for a single combo;
+/// - a `:weight` suffix between 0 and 1, as in `AKo:0.5`.
+///
+/// Later entries override the weight of earlier ones.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Range {
+    combos: BTreeMap<Combo, f64>,
+}
+
+impl Range {
+    pub fn new() -> Range {
+        Range::default()
+    }
+
+    pub fn insert(&mut self, card_1: Card, card_2: Card, weight: f64) {
+        self.combos.insert(combo(card_1, card_2), weight);
+    }
+
+    pub fn insert_class(&mut self, class: HandClass, weight: f64) {
+        for (card_1, card_2) in class.combos() {
+            self.insert(card_1, card_2, weight);
+        }
+    }
+
+    /// Weight of a combo, or 0 if it is not in the range.
+    pub fn weight(&self, card_1: Card, card_2: Card) -> f64 {
+        self.combos
+            .get(&combo(card_1, card_2))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    pub fn contains(&self, card_1: Card, card_2: Card) -> bool {
+        self.combos.contains_key(&combo(card_1, card_2))
+    }
+
+    /// Weight shared by every combo of `class`, or `None` if the class is
+    /// only partly in the range or its combos have different weights.
+    pub fn class_weight(&self, class: HandClass) -> Option<f64> {
+        let mut weights = class
+            .combos()
+            .into_iter()
+            .map(|(card_1, card_2)| self.combos.get(&(card_1, card_2)).copied());
+        let first = weights.next()??;
+        for weight in weights {
+            if weight? != first {
+                return None;
+            }
+        }
+        Some(first)
+    }
+
+    /// Every combo in the range with its weight.
+    pub fn combos(&self) -> impl Iterator<Item = (Combo, f64)> + '_ {
+        self.combos.iter().map(|(&combo, &weight)| (combo, weight))
+    }
+
+    /// Every combo in the range, ignoring weights.
+    pub fn holdings(&self) -> Vec<Combo> {
+        self.combos.keys().copied().collect()
+    }
+
+    /// Number of distinct combos, ignoring weights.
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Number of combos counted by weight, e.g. 6 for `AKo:0.5`.
+    pub fn combo_count(&self) -> f64 {
+        self.combos.values().sum()
+    }
+
+    /// Drops every combo that uses one of `cards`, such as the board or
+    /// known dead cards.
+    pub fn remove_blocked(&mut self, cards: &[Card]) {
+        self.combos
+            .retain(|(card_1, card_2), _| !cards.contains(card_1) && !cards.contains(card_2));
+    }
+}
+
+fn combo(card_1: Card, card_2: Card) -> Combo {
+    (card_1.max(card_2), card_1.min(card_2))
+}
+
+impl FromStr for Range {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = Range::new();
+        for entry in s.split(|c: char| c == ',' || c.is_whitespace()) {
+            if entry.is_empty() {
+                continue;
+            }
+
+            let (token, weight) = match entry.split_once(':') {
+                Some((token, weight)) => match weight.parse::<f64>() {
+                    Ok(weight) if (0.0..=1.0).contains(&weight) => (token, weight),
+                    _ => return Err(format!("Invalid weight: {}", entry)),
+                },
+                None => (entry, 1.0),
+            };
+
+            if let Some((card_1, card_2)) = parse_combo(token) {
+                range.insert(card_1, card_2, weight);
+                continue;
+            }
+            for class in parse_classes(token)? {
+                range.insert_class(class, weight);
+            }
+        }
+        Ok(range)
+    }
+}
+
+fn parse_combo(token: &str) -> Option<Combo> {
+    if token.len() != 4 || token.contains(['+', '-']) {
+        return None;
+    }
+    match *token.parse::<CardList>().ok()? {
+        [card_1, card_2] => Some(combo(card_1, card_2)),
+        _ => None,
+    }
+}
+
+/// A hand class pattern such as "AK", "AKs" or "QQ", with `None` standing
+/// for both suited and offsuit.
+type Pattern = (Rank, Rank, Option<SuitCombination>);
+
+fn parse_pattern(s: &str) -> Result<Pattern, String> {
+    let chars: Vec<char> = s.chars().collect();
+    let (rank_1, rank_2, suit_combination) = match chars.as_slice() {
+        [r1, r2] => (r1, r2, None),
+        [r1, r2, suit] if r1 != r2 => (r1, r2, Some(suit.to_string().parse()?)),
+        _ => return Err(format!("Invalid hand class: {}", s)),
+    };
+    let rank_1: Rank = rank_1.to_string().parse()?;
+    let rank_2: Rank = rank_2.to_string().parse()?;
+    Ok((rank_1.max(rank_2), rank_1.min(rank_2), suit_combination))
+}
+
+fn parse_classes(token: &str) -> Result<Vec<HandClass>, String> {
+    let (high, lows, suit_combination) = if let Some(base) = token.strip_suffix('+') {
+        let (high, low, suit_combination) = parse_pattern(base)?;
+        if high == low {
+            return Ok(ranks_between(low, Rank::Ace)
+                .into_iter()
+                .map(HandClass::pair)
+                .collect());
+        }
+        (high, ranks_between(low, previous(high)), suit_combination)
+    } else if let Some((from, to)) = token.split_once('-') {
+        let (high_1, low_1, suit_1) = parse_pattern(from)?;
+        let (high_2, low_2, suit_2) = parse_pattern(to)?;
+        if high_1 == low_1 && high_2 == low_2 {
+            let ranks = ranks_between(high_1.min(high_2), high_1.max(high_2));
+            return Ok(ranks.into_iter().map(HandClass::pair).collect());
+        }
+        if high_1 != high_2 || suit_1 != suit_2 || high_1 == low_1 || high_2 == low_2 {
+            return Err(format!("Invalid span: {}", token));
+        }
+        (
+            high_1,
+            ranks_between(low_1.min(low_2), low_1.max(low_2)),
+            suit_1,
+        )
+    } else {
+        let (high, low, suit_combination) = parse_pattern(token)?;
+        (high, ranks_between(low, low), suit_combination)
+    };
+
+    let suit_combinations = match suit_combination {
+        Some(suit_combination) => vec![suit_combination],
+        None => vec![SuitCombination::Suited, SuitCombination::Offsuit],
+    };
+    let mut classes = Vec::new();
+    for low in lows {
+        for &suit_combination in &suit_combinations {
+            classes.push(HandClass::new(high, low, suit_combination));
+        }
+    }
+    Ok(classes)
+}
+
+/// Ranks from `low` to `high` inclusive, empty if `low` is above `high`.
+fn ranks_between(low: Rank, high: Rank) -> Vec<Rank> {
+    (low.index()..=high.index())
+        .filter_map(Rank::from_index)
+        .collect()
+}
+
+fn previous(rank: Rank) -> Rank {
+    Rank::from_index(rank.index().saturating_sub(1)).unwrap()
+}
+
+/// Prints the range in its most compact form, e.g. "TT+, AQs+, A5s-A2s, KJo".
+///
+/// Pairs come first, then hands by their higher rank. Complete classes are
+/// grouped into `+` and `-` spans, and anything else is listed combo by combo.
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut tokens = Vec::new();
+
+        let pairs: Vec<(String, Option<f64>)> = RANKS
+            .iter()
+            .map(|&rank| {
+                let class = HandClass::pair(rank);
+                (class.to_string(), self.class_weight(class))
+            })
+            .collect();
+        spans(&pairs, &mut tokens);
+
+        for (i, &high) in RANKS.iter().enumerate() {
+            let mut any = Vec::new();
+            let mut suited = Vec::new();
+            let mut offsuit = Vec::new();
+            for &low in &RANKS[i + 1..] {
+                let s = HandClass::new(high, low, SuitCombination::Suited);
+                let o = HandClass::new(high, low, SuitCombination::Offsuit);
+                let (s_weight, o_weight) = (self.class_weight(s), self.class_weight(o));
+                if s_weight.is_some() && s_weight == o_weight {
+                    any.push((format!("{}{}", high, low), s_weight));
+                    suited.push((s.to_string(), None));
+                    offsuit.push((o.to_string(), None));
+                } else {
+                    any.push((format!("{}{}", high, low), None));
+                    suited.push((s.to_string(), s_weight));
+                    offsuit.push((o.to_string(), o_weight));
+                }
+            }
+            spans(&any, &mut tokens);
+            spans(&suited, &mut tokens);
+            spans(&offsuit, &mut tokens);
+        }
+
+        let mut partial: HashMap<HandClass, Vec<(Combo, f64)>> = HashMap::new();
+        for (combo, weight) in self.combos() {
+            let class = HandClass::of(combo.0, combo.1);
+            if self.class_weight(class).is_none() {
+                partial.entry(class).or_default().push((combo, weight));
+            }
+        }
+        for class in HandClass::all() {
+            for ((card_1, card_2), weight) in
+                partial.remove(&class).unwrap_or_default().into_iter().rev()
+            {
+                tokens.push(with_weight(format!("{}{}", card_1, card_2), weight));
+            }
+        }
+
+        write!(f, "{}", tokens.join(", "))
+    }
+}
+
+/// Groups runs of consecutive labels with the same weight. The first label
+/// is the top of its ladder (AA, or AK for aces), so a run starting there is
+/// written with a `+`.
+fn spans(labels: &[(String, Option<f64>)], tokens: &mut Vec<String>) {
+    let mut start = 0;
+    while start < labels.len() {
+        let weight = match labels[start].1 {
+            Some(weight) => weight,
+            None => {
+                start += 1;
+                continue;
+            }
+        };
+        let mut end = start;
+        while end + 1 < labels.len() && labels[end + 1].1 == Some(weight) {
+            end += 1;
+        }
+
+        let token = if start == end {
+            labels[start].0.clone()
+        } else if start == 0 {
+            format!("{}+", labels[end].0)
+        } else {
+            format!("{}-{}", labels[start].0, labels[end].0)
+        };
+        tokens.push(with_weight(token, weight));
+        start = end + 1;
+    }
+}
+
+fn with_weight(token: String, weight: f64) -> String {
+    if weight == 1.0 {
+        token
+    } else {
+        format!("{}:{}", token, weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(s: &str) -> Card {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let range: Range = "TT+, AQs+, KJo, A5s-A2s".parse().unwrap();
+        assert_eq!(range.len(), 5 * 6 + 2 * 4 + 12 + 4 * 4);
+        assert!(range.contains(card("Ah"), card("Ad")));
+        assert!(range.contains(card("Ks"), card("As")));
+        assert!(range.contains(card("Kh"), card("Jc")));
+        assert!(!range.contains(card("Kh"), card("Jh")));
+        assert!(range.contains(card("As"), card("3s")));
+        assert!(!range.contains(card("As"), card("Js")));
+        assert!(!range.contains(card("9h"), card("9d")));
+
+        let range: Range = "77-99 KT+ AhKh".parse().unwrap();
+        assert_eq!(range.len(), 3 * 6 + 3 * 16 + 1);
+        assert!(range.contains(card("Kh"), card("Ah")));
+    }
+
+    #[test]
+    fn test_weights() {
+        let range: Range = "AK, AKo:0.5".parse().unwrap();
+        assert_eq!(range.len(), 16);
+        assert_eq!(range.combo_count(), 4.0 + 6.0);
+        assert_eq!(range.weight(card("As"), card("Kd")), 0.5);
+        assert_eq!(range.weight(card("As"), card("Ks")), 1.0);
+        assert_eq!(range.weight(card("As"), card("Qs")), 0.0);
+    }
+
+    #[test]
+    fn test_remove_blocked() {
+        let mut range: Range = "AA, KK".parse().unwrap();
+        range.remove_blocked(&[card("As"), card("Kd"), card("Kh")]);
+        assert_eq!(range.len(), 3 + 1);
+    }
+
+    #[test]
+    fn test_display() {
+        let range: Range = "A2s-A5s, KJo, AKs, AQs, 88, TT+, 99, AKo:0.5, AhKh"
+            .parse()
+            .unwrap();
+        assert_eq!(range.to_string(), "88+, AQs+, A5s-A2s, AKo:0.5, KJo");
+
+        let range: Range = "QQ-JJ, KQ, KJ, T9s, 76s:0.25, QhJh".parse().unwrap();
+        assert_eq!(range.to_string(), "QQ-JJ, KJ+, T9s, 76s:0.25, QhJh");
+
+        let range: Range = "AKo, AsKs, AhKh".parse().unwrap();
+        assert_eq!(range.to_string(), "AKo, AsKs, AhKh");
+
+        for s in ["88+, AQs+, A5s-A2s, AKo:0.5, KJo", "22+, A2+, K9s-K7s, 65o"] {
+            let range: Range = s.parse().unwrap();
+            assert_eq!(range.to_string(), s);
+            assert_eq!(range.to_string().parse::<Range>().unwrap(), range);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!("AKx".parse::<Range>().is_err());
+        assert!("AK:2".parse::<Range>().is_err());
+        assert!("AKs-QJs".parse::<Range>().is_err());
+        assert!("AKs-A2o".parse::<Range>().is_err());
+        assert!("Q".parse::<Range>().is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{Action, Position};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// What happened before the player's decision.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum Scenario {
+    /// Folded to us: raise first in or fold.
+    #[default]
+    Unopened,
+    /// Facing a single open, from the given position if the chart says.
+    VsOpen(Option<Position>),
+    /// We opened and were 3-bet.
+    VsThreeBet,
+    /// One or more players limped in.
+    VsLimpers,
+    /// Facing an open and at least one call.
+    Squeeze,
+}
+
+impl Scenario {
+    /// The raise available in this scenario: an open, a 3-bet over an open
+    /// or a 4-bet over a 3-bet.
+    pub fn raise(&self) -> Action {
+        match self {
+            Scenario::Unopened | Scenario::VsLimpers => Action::Raise,
+            Scenario::VsOpen(_) | Scenario::Squeeze => Action::ThreeBet,
+            Scenario::VsThreeBet => Action::FourBet,
+        }
+    }
+}
+
+/// Parses "rfi", "vs-open", "vs-open-co", "vs-3bet", "vs-limp" and
+/// "squeeze".
+impl FromStr for Scenario {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "rfi" => Ok(Scenario::Unopened),
+            "vs-3bet" => Ok(Scenario::VsThreeBet),
+            "vs-limp" => Ok(Scenario::VsLimpers),
+            "squeeze" => Ok(Scenario::Squeeze),
+            "vs-open" => Ok(Scenario::VsOpen(None)),
+            other => match other.strip_prefix("vs-open-") {
+                Some(position) => Ok(Scenario::VsOpen(Some(position.parse()?))),
+                None => Err(format!("Invalid scenario: {}", s)),
+            },
+        }
+    }
+}
+
+impl Display for Scenario {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scenario::Unopened => write!(f, "unopened"),
+            Scenario::VsOpen(None) => write!(f, "vs open"),
+            Scenario::VsOpen(Some(position)) => write!(f, "vs {} open", position),
+            Scenario::VsThreeBet => write!(f, "vs 3-bet"),
+            Scenario::VsLimpers => write!(f, "vs limpers"),
+            Scenario::Squeeze => write!(f, "squeeze"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("rfi".parse(), Ok(Scenario::Unopened));
+        assert_eq!("vs-open".parse(), Ok(Scenario::VsOpen(None)));
+        assert_eq!(
+            "vs-open-utg+1".parse(),
+            Ok(Scenario::VsOpen(Some(Position::UnderTheGun1)))
+        );
+        assert_eq!("vs-3bet".parse(), Ok(Scenario::VsThreeBet));
+        assert!("vs-open-dealer".parse::<Scenario>().is_err());
+        assert_eq!(
+            Scenario::VsOpen(Some(Position::Cutoff)).to_string(),
+            "vs CO open"
+        );
+        assert_eq!(Scenario::Squeeze.raise(), Action::ThreeBet);
+    }
+}
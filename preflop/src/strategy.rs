@@ -0,0 +1,211 @@
+use crate::scenario::Scenario;
+use crate::Action;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Actions taken less often than this are treated as mistakes when grading.
+pub const MIN_FREQUENCY: f64 = 0.1;
+
+/// How often each action is taken with a hand, as solver charts give it.
+///
+/// Written as a single action such as `r`, or as a mix of actions with
+/// frequencies between 0 and 1 separated by slashes, as in `r:0.6/c:0.4`.
+/// The frequencies of a mix must add up to 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Strategy {
+    /// Sorted from the most to the least frequent action.
+    actions: Vec<(Action, f64)>,
+}
+
+/// How well an answer matches a [`Strategy`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grade {
+    /// How often the strategy takes the chosen action.
+    pub frequency: f64,
+    /// The frequency relative to the strategy's most frequent action, so the
+    /// main action scores 1 and actions it never takes score 0.
+    pub score: f64,
+}
+
+impl Grade {
+    pub fn is_correct(&self) -> bool {
+        self.frequency >= MIN_FREQUENCY
+    }
+}
+
+impl Strategy {
+    pub fn pure(action: Action) -> Strategy {
+        Strategy {
+            actions: vec![(action, 1.0)],
+        }
+    }
+
+    /// The most frequent action.
+    pub fn primary(&self) -> Action {
+        self.actions[0].0
+    }
+
+    pub fn actions(&self) -> &[(Action, f64)] {
+        &self.actions
+    }
+
+    pub fn frequency(&self, action: Action) -> f64 {
+        self.actions
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map_or(0.0, |(_, frequency)| *frequency)
+    }
+
+    pub fn is_mixed(&self) -> bool {
+        self.actions.len() > 1
+    }
+
+    /// The strategy as played in `scenario`: a plain raise becomes the raise
+    /// the scenario allows, and a 3-bet or 4-bet the scenario doesn't allow
+    /// is an error.
+    pub fn in_scenario(self, scenario: Scenario) -> Result<Strategy, String> {
+        let mut actions: Vec<(Action, f64)> = Vec::new();
+        for (action, frequency) in self.actions {
+            let action = match action {
+                Action::Raise => scenario.raise(),
+                Action::ThreeBet | Action::FourBet if action != scenario.raise() => {
+                    return Err(format!("Invalid action for {}: {}", scenario, action));
+                }
+                action => action,
+            };
+            if actions.iter().any(|(a, _)| *a == action) {
+                return Err(format!("Action listed twice: {}", action));
+            }
+            actions.push((action, frequency));
+        }
+        Ok(Strategy { actions })
+    }
+
+    pub fn grade(&self, action: Action) -> Grade {
+        let frequency = self.frequency(action);
+        Grade {
+            frequency,
+            score: frequency / self.actions[0].1,
+        }
+    }
+}
+
+impl From<Action> for Strategy {
+    fn from(action: Action) -> Self {
+        Strategy::pure(action)
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut actions: Vec<(Action, f64)> = Vec::new();
+        for part in s.split('/') {
+            let (action, frequency) = match part.trim().split_once(':') {
+                Some((action, frequency)) => {
+                    let frequency: f64 = frequency
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid frequency: {}", frequency))?;
+                    if !(0.0..=1.0).contains(&frequency) {
+                        return Err(format!("Invalid frequency: {}", frequency));
+                    }
+                    (action.trim().parse()?, frequency)
+                }
+                None => (part.trim().parse()?, 1.0),
+            };
+            if actions.iter().any(|(a, _)| *a == action) {
+                return Err(format!("Action listed twice: {}", s));
+            }
+            actions.push((action, frequency));
+        }
+
+        let total: f64 = actions.iter().map(|(_, frequency)| frequency).sum();
+        if (total - 1.0).abs() > 0.01 {
+            return Err(format!("Frequencies add up to {}, not 1: {}", total, s));
+        }
+        actions.retain(|(_, frequency)| *frequency > 0.0);
+        actions.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(Strategy { actions })
+    }
+}
+
+/// Writes "raise" for a pure strategy and "raise 60% / call 40%" for a mix.
+impl Display for Strategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.is_mixed() {
+            return write!(f, "{}", self.primary());
+        }
+        for (i, (action, frequency)) in self.actions.iter().enumerate() {
+            if i > 0 {
+                write!(f, " / ")?;
+            }
+            write!(f, "{} {:.0}%", action, frequency * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let mixed: Strategy = "c:0.4/r:0.6".parse().unwrap();
+        assert_eq!(mixed.primary(), Action::Raise);
+        assert_eq!(mixed.frequency(Action::Call), 0.4);
+        assert_eq!(mixed.frequency(Action::Fold), 0.0);
+        assert_eq!(mixed.to_string(), "raise 60% / call 40%");
+
+        let pure: Strategy = "f".parse().unwrap();
+        assert!(!pure.is_mixed());
+        assert_eq!(pure.to_string(), "fold");
+
+        assert!("r:0.6/c:0.3".parse::<Strategy>().is_err());
+        assert!("r:0.5/r:0.5".parse::<Strategy>().is_err());
+        assert!("r:1.5".parse::<Strategy>().is_err());
+    }
+
+    #[test]
+    fn test_grade() {
+        let strategy: Strategy = "3b:0.55/c:0.4/f:0.05".parse().unwrap();
+        let three_bet = strategy.grade(Action::ThreeBet);
+        assert!(three_bet.is_correct());
+        assert_eq!(three_bet.score, 1.0);
+
+        let call = strategy.grade(Action::Call);
+        assert!(call.is_correct());
+        assert!((call.score - 0.4 / 0.55).abs() < 1e-9);
+
+        assert!(!strategy.grade(Action::Fold).is_correct());
+        assert_eq!(strategy.grade(Action::Jam).score, 0.0);
+    }
+
+    #[test]
+    fn test_in_scenario() {
+        let open: Strategy = "r:0.7/c:0.3".parse().unwrap();
+        let three_bet = open.in_scenario(Scenario::VsOpen(None)).unwrap();
+        assert_eq!(three_bet.primary(), Action::ThreeBet);
+        assert_eq!(three_bet.to_string(), "3-bet 70% / call 30%");
+
+        let four_bet: Strategy = "r".parse().unwrap();
+        assert_eq!(
+            four_bet.in_scenario(Scenario::VsThreeBet).unwrap(),
+            Strategy::pure(Action::FourBet)
+        );
+
+        let three_bet: Strategy = "3b".parse().unwrap();
+        assert_eq!(
+            three_bet.clone().in_scenario(Scenario::Unopened),
+            Err("Invalid action for unopened: 3-bet".to_string())
+        );
+        assert!(three_bet.in_scenario(Scenario::VsLimpers).is_err());
+        let four_bet: Strategy = "4b".parse().unwrap();
+        assert!(four_bet.in_scenario(Scenario::Squeeze).is_err());
+
+        let twice: Strategy = "r:0.5/3b:0.5".parse().unwrap();
+        assert!(twice.in_scenario(Scenario::VsOpen(None)).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use crate::rank::Rank;
+use crate::suit::Suit;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// Number of cards in a standard deck.
+pub const DECK_SIZE: usize = 52;
+
+/// A playing card packed into a single byte as `rank * 4 + suit`.
+///
+/// Cards are ordered by rank first and suit second, so sorting a hand puts
+/// the deuces first and the aces last.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Card(u8);
+
+impl Card {
+    pub fn new(rank: Rank, suit: Suit) -> Card {
+        Card((rank.index() * 4 + suit.index()) as u8)
+    }
+
+    pub fn rank(self) -> Rank {
+        Rank::from_index(self.0 as usize / 4).expect("card index is always in range")
+    }
+
+    pub fn suit(self) -> Suit {
+        Suit::from_index(self.0 as usize % 4).expect("card index is always in range")
+    }
+
+    /// Position of the card in a sorted deck, from 0 (2♣) to 51 (A♠).
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Inverse of [`Card::index`].
+    pub fn from_index(index: usize) -> Option<Card> {
+        if index < DECK_SIZE {
+            Some(Card(index as u8))
+        } else {
+            None
+        }
+    }
+
+    /// Every card of a standard deck in ascending order.
+    pub fn all() -> impl Iterator<Item = Card> {
+        (0..DECK_SIZE as u8).map(Card)
+    }
+}
+
+impl From<(Rank, Suit)> for Card {
+    fn from((rank, suit): (Rank, Suit)) -> Self {
+        Card::new(rank, suit)
+    }
+}
+
+impl From<Card> for (Rank, Suit) {
+    fn from(card: Card) -> Self {
+        (card.rank(), card.suit())
+    }
+}
+
+/// Prints the card in standard notation ("As", "Td", "7c"). The alternate
+/// form (`{:#}`) uses the Unicode suit symbol instead ("A♠").
+impl Display for Card {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            write!(f, "{}{}", self.rank(), self.suit())
+        } else {
+            write!(f, "{}{}", self.rank(), self.suit().letter())
+        }
+    }
+}
+
+/// Parses a single card in standard ("As") or Unicode ("A♠") notation.
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cards = tokenize(s.trim())?;
+        match (cards.pop(), cards.is_empty()) {
+            (Some(card), true) => Ok(card),
+            (None, _) => Err(ParseCardError::Empty),
+            (Some(_), false) => Err(ParseCardError::TooManyCards(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    Empty,
+    InvalidRank(String),
+    InvalidSuit(String),
+    MissingSuit(String),
+    TooManyCards(String),
+    Duplicate(Card),
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::Empty => write!(f, "No card given"),
+            ParseCardError::InvalidRank(s) => write!(f, "Invalid rank: {}", s),
+            ParseCardError::InvalidSuit(s) => write!(f, "Invalid suit: {}", s),
+            ParseCardError::MissingSuit(s) => write!(f, "Missing suit after rank: {}", s),
+            ParseCardError::TooManyCards(s) => write!(f, "Expected a single card: {}", s),
+            ParseCardError::Duplicate(card) => write!(f, "Duplicate card: {}", card),
+        }
+    }
+}
+
+impl Error for ParseCardError {}
+
+/// An ordered list of cards, such as a board or a holding.
+///
+/// Parses "AsKd", "Ah 7c 2d", "Ah,7c,2d" and "[Qs Jh Th]" alike, rejecting
+/// duplicates, and prints the cards separated by spaces.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CardList(Vec<Card>);
+
+impl CardList {
+    pub fn into_inner(self) -> Vec<Card> {
+        self.0
+    }
+}
+
+impl Deref for CardList {
+    type Target = [Card];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<Card>> for CardList {
+    fn from(cards: Vec<Card>) -> Self {
+        CardList(cards)
+    }
+}
+
+impl From<CardList> for Vec<Card> {
+    fn from(cards: CardList) -> Self {
+        cards.0
+    }
+}
+
+impl FromStr for CardList {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .unwrap_or(s);
+
+        let cards = tokenize(s)?;
+        let mut seen = HashSet::new();
+        for card in &cards {
+            if !seen.insert(*card) {
+                return Err(ParseCardError::Duplicate(*card));
+            }
+        }
+        Ok(CardList(cards))
+    }
+}
+
+impl Display for CardList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, card) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            Display::fmt(card, f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits a run of rank/suit pairs into cards, skipping whitespace and commas.
+fn tokenize(s: &str) -> Result<Vec<Card>, ParseCardError> {
+    let mut cards = Vec::new();
+    let mut chars = s
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .peekable();
+
+    while let Some(first) = chars.next() {
+        let mut rank = first.to_string();
+        if first == '1' && chars.peek() == Some(&'0') {
+            rank.push(chars.next().unwrap());
+        }
+        let rank: Rank = rank
+            .parse()
+            .map_err(|_| ParseCardError::InvalidRank(rank))?;
+
+        let suit = match chars.next() {
+            Some(suit) => suit.to_string(),
+            None => return Err(ParseCardError::MissingSuit(rank.to_string())),
+        };
+        let suit: Suit = suit
+            .parse()
+            .map_err(|_| ParseCardError::InvalidSuit(suit))?;
+
+        cards.push(Card::new(rank, suit));
+    }
+
+    Ok(cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rank::RANKS;
+    use crate::suit::SUITS;
+
+    #[test]
+    fn test_round_trip() {
+        for rank in RANKS {
+            for suit in SUITS.iter() {
+                let card = Card::new(rank, *suit);
+                assert_eq!(card.rank(), rank);
+                assert_eq!(card.suit(), *suit);
+                assert_eq!(Card::from_index(card.index()), Some(card));
+            }
+        }
+        assert_eq!(Card::from_index(DECK_SIZE), None);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(Card::new(Rank::Ace, Suit::Clubs) > Card::new(Rank::King, Suit::Spades));
+        assert!(Card::new(Rank::Two, Suit::Spades) > Card::new(Rank::Two, Suit::Hearts));
+        assert_eq!(Card::all().count(), DECK_SIZE);
+        assert_eq!(Card::all().next(), Some(Card::new(Rank::Two, Suit::Clubs)));
+        assert_eq!(Card::all().last(), Some(Card::new(Rank::Ace, Suit::Spades)));
+    }
+
+    #[test]
+    fn test_parse_card() {
+        assert_eq!("As".parse(), Ok(Card::new(Rank::Ace, Suit::Spades)));
+        assert_eq!("Td".parse(), Ok(Card::new(Rank::Ten, Suit::Diamonds)));
+        assert_eq!("10h".parse(), Ok(Card::new(Rank::Ten, Suit::Hearts)));
+        assert_eq!("7♣".parse(), Ok(Card::new(Rank::Seven, Suit::Clubs)));
+        assert_eq!("".parse::<Card>(), Err(ParseCardError::Empty));
+        assert_eq!(
+            "Xs".parse::<Card>(),
+            Err(ParseCardError::InvalidRank("X".to_string()))
+        );
+        assert_eq!(
+            "Ax".parse::<Card>(),
+            Err(ParseCardError::InvalidSuit("x".to_string()))
+        );
+        assert_eq!(
+            "A".parse::<Card>(),
+            Err(ParseCardError::MissingSuit("A".to_string()))
+        );
+        assert_eq!(
+            "AsKd".parse::<Card>(),
+            Err(ParseCardError::TooManyCards("AsKd".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_display_card() {
+        let card = Card::new(Rank::Ten, Suit::Spades);
+        assert_eq!(card.to_string(), "Ts");
+        assert_eq!(format!("{:#}", card), "T♠");
+        for card in Card::all() {
+            assert_eq!(card.to_string().parse(), Ok(card));
+            assert_eq!(format!("{:#}", card).parse(), Ok(card));
+        }
+    }
+
+    #[test]
+    fn test_parse_card_list() {
+        let expected = vec![
+            Card::new(Rank::Queen, Suit::Spades),
+            Card::new(Rank::Jack, Suit::Hearts),
+            Card::new(Rank::Ten, Suit::Hearts),
+        ];
+        for s in ["QsJhTh", "Qs Jh Th", "[Qs Jh Th]", "Qs, Jh, Th", "Q♠J♥T♥"] {
+            let cards: CardList = s.parse().unwrap();
+            assert_eq!(cards.to_vec(), expected);
+        }
+
+        let cards: CardList = "[Qs Jh Th]".parse().unwrap();
+        assert_eq!(cards.to_string(), "Qs Jh Th");
+        assert_eq!(format!("{:#}", cards), "Q♠ J♥ T♥");
+        assert!("".parse::<CardList>().unwrap().is_empty());
+        assert_eq!(
+            "Ah 7c Ah".parse::<CardList>(),
+            Err(ParseCardError::Duplicate(Card::new(
+                Rank::Ace,
+                Suit::Hearts
+            )))
+        );
+    }
+}
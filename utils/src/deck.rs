@@ -0,0 +1,184 @@
+use crate::card::Card;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// A deck of cards dealt from the top.
+///
+/// Dead cards removed with [`Deck::remove`] stay out of the deck across
+/// [`Deck::reset`]. A deck built with [`Deck::from_seed`] shuffles the same
+/// way on every run, which keeps drills and tests reproducible.
+#[derive(Debug, Clone)]
+pub struct Deck {
+    cards: Vec<Card>,
+    dead: Vec<Card>,
+    next: usize,
+    rng: StdRng,
+}
+
+impl Deck {
+    /// A full, unshuffled deck using an entropy-seeded RNG.
+    pub fn new() -> Deck {
+        Deck::with_rng(StdRng::from_entropy())
+    }
+
+    /// A full, unshuffled deck whose shuffles are determined by `seed`.
+    pub fn from_seed(seed: u64) -> Deck {
+        Deck::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    pub fn with_rng(rng: StdRng) -> Deck {
+        Deck {
+            cards: Card::all().collect(),
+            dead: Vec::new(),
+            next: 0,
+            rng,
+        }
+    }
+
+    /// Takes the given cards out of the deck for good. Cards already dealt
+    /// stay dealt, and the rest keep their order.
+    pub fn remove(&mut self, dead: &[Card]) {
+        for card in dead {
+            if !self.dead.contains(card) {
+                self.dead.push(*card);
+            }
+        }
+        let undealt = self.cards.split_off(self.next);
+        let dead = &self.dead;
+        self.cards
+            .extend(undealt.into_iter().filter(|card| !dead.contains(card)));
+    }
+
+    /// Puts every dealt and burnt card back, leaving the dead cards out.
+    /// The deck is left unshuffled.
+    pub fn reset(&mut self) {
+        self.cards = Card::all()
+            .filter(|card| !self.dead.contains(card))
+            .collect();
+        self.next = 0;
+    }
+
+    /// Fisher–Yates shuffle of the cards that have not been dealt yet.
+    pub fn shuffle(&mut self) {
+        let remaining = &mut self.cards[self.next..];
+        for i in (1..remaining.len()).rev() {
+            let j = self.rng.gen_range(0..=i);
+            remaining.swap(i, j);
+        }
+    }
+
+    /// Deals `n` cards from the top, or `None` if fewer than `n` are left.
+    pub fn deal(&mut self, n: usize) -> Option<Vec<Card>> {
+        let cards = self.peek(n)?.to_vec();
+        self.next += n;
+        Some(cards)
+    }
+
+    pub fn deal_one(&mut self) -> Option<Card> {
+        let card = *self.cards.get(self.next)?;
+        self.next += 1;
+        Some(card)
+    }
+
+    /// Discards the top card.
+    pub fn burn(&mut self) -> Option<Card> {
+        self.deal_one()
+    }
+
+    /// The next `n` cards in the order they would be dealt.
+    pub fn peek(&self, n: usize) -> Option<&[Card]> {
+        self.cards.get(self.next..self.next + n)
+    }
+
+    /// The cards that have not been dealt yet.
+    pub fn cards(&self) -> &[Card] {
+        &self.cards[self.next..]
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.cards().contains(card)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len() - self.next
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{CardList, DECK_SIZE};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_deal_and_burn() {
+        let mut deck = Deck::from_seed(7);
+        deck.shuffle();
+
+        let top = deck.peek(3).unwrap().to_vec();
+        assert_eq!(deck.deal(3), Some(top));
+        assert_eq!(deck.len(), DECK_SIZE - 3);
+
+        let next = deck.peek(1).unwrap()[0];
+        assert_eq!(deck.burn(), Some(next));
+        assert!(!deck.contains(&next));
+
+        assert_eq!(deck.deal(DECK_SIZE), None);
+        assert_eq!(deck.deal(DECK_SIZE - 4).map(|cards| cards.len()), Some(48));
+        assert!(deck.is_empty());
+        assert_eq!(deck.deal_one(), None);
+    }
+
+    #[test]
+    fn test_dead_cards() {
+        let dead: CardList = "As Kd 7c".parse().unwrap();
+        let mut deck = Deck::from_seed(1);
+        deck.remove(&dead);
+        deck.shuffle();
+
+        let dealt: HashSet<Card> = deck.deal(DECK_SIZE - 3).unwrap().into_iter().collect();
+        assert_eq!(dealt.len(), DECK_SIZE - 3);
+        assert!(dead.iter().all(|card| !dealt.contains(card)));
+
+        deck.reset();
+        assert_eq!(deck.len(), DECK_SIZE - 3);
+        assert!(dead.iter().all(|card| !deck.contains(card)));
+    }
+
+    #[test]
+    fn test_remove_keeps_dealt_cards_out() {
+        let mut deck = Deck::from_seed(3);
+        deck.shuffle();
+        let dealt = deck.deal(2).unwrap();
+        let next = deck.peek(3).unwrap().to_vec();
+
+        deck.remove(&[dealt[0], next[1]]);
+        assert_eq!(deck.len(), DECK_SIZE - 3);
+        assert!(!deck.contains(&dealt[1]));
+        assert_eq!(deck.peek(2).unwrap(), [next[0], next[2]]);
+
+        deck.reset();
+        assert_eq!(deck.len(), DECK_SIZE - 2);
+        assert!(deck.contains(&dealt[1]));
+    }
+
+    #[test]
+    fn test_seeded_shuffle_is_reproducible() {
+        let mut a = Deck::from_seed(42);
+        let mut b = Deck::from_seed(42);
+        a.shuffle();
+        b.shuffle();
+        assert_eq!(a.cards(), b.cards());
+        assert_ne!(a.cards(), Deck::from_seed(42).cards());
+    }
+}
@@ -1,2 +1,4 @@
+pub mod card;
+pub mod deck;
 pub mod rank;
 pub mod suit;
@@ -17,9 +17,10 @@ pub const RANKS: [Rank; 13] = [
     Rank::Two,
 ];
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+/// Ranks are ordered from deuce to ace, so the derived ordering is the
+/// usual poker ordering with the ace high.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub enum Rank {
-    Ace,
     Two,
     Three,
     Four,
@@ -32,6 +33,24 @@ pub enum Rank {
     Jack,
     Queen,
     King,
+    Ace,
+}
+
+impl Rank {
+    /// Position of the rank in ascending order, from 0 (deuce) to 12 (ace).
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Inverse of [`Rank::index`].
+    pub fn from_index(index: usize) -> Option<Rank> {
+        RANKS.get(12usize.checked_sub(index)?).copied()
+    }
+
+    /// Face value of the rank, from 2 (deuce) to 14 (ace).
+    pub fn value(self) -> u8 {
+        self as u8 + 2
+    }
 }
 
 impl FromStr for Rank {
@@ -39,7 +58,7 @@ impl FromStr for Rank {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "A" => Ok(Rank::Ace),
+            "A" | "a" => Ok(Rank::Ace),
             "2" => Ok(Rank::Two),
             "3" => Ok(Rank::Three),
             "4" => Ok(Rank::Four),
@@ -48,10 +67,10 @@ impl FromStr for Rank {
             "7" => Ok(Rank::Seven),
             "8" => Ok(Rank::Eight),
             "9" => Ok(Rank::Nine),
-            "T" => Ok(Rank::Ten),
-            "J" => Ok(Rank::Jack),
-            "Q" => Ok(Rank::Queen),
-            "K" => Ok(Rank::King),
+            "T" | "t" | "10" => Ok(Rank::Ten),
+            "J" | "j" => Ok(Rank::Jack),
+            "Q" | "q" => Ok(Rank::Queen),
+            "K" | "k" => Ok(Rank::King),
             _ => Err(format!("Invalid rank: {}", s)),
         }
     }
@@ -6,24 +6,46 @@ lazy_static! {
     pub static ref SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub enum Suit {
+    Clubs,
     Diamonds,
     Hearts,
-    Clubs,
     Spades,
 }
 
+impl Suit {
+    /// Position of the suit in [`SUITS`], from 0 to 3.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Single-letter name of the suit used in card notation such as "As".
+    pub fn letter(self) -> char {
+        match self {
+            Suit::Clubs => 'c',
+            Suit::Diamonds => 'd',
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+        }
+    }
+
+    /// Inverse of [`Suit::index`].
+    pub fn from_index(index: usize) -> Option<Suit> {
+        SUITS.get(index).copied()
+    }
+}
+
 impl FromStr for Suit {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "clubs" => Ok(Suit::Clubs),
-            "diamonds" => Ok(Suit::Diamonds),
-            "hearts" => Ok(Suit::Hearts),
-            "spades" => Ok(Suit::Spades),
-            _ => Err(format!("Invalid rank: {}", s)),
+            "clubs" | "c" | "♣" | "♧" => Ok(Suit::Clubs),
+            "diamonds" | "d" | "♦" | "♢" => Ok(Suit::Diamonds),
+            "hearts" | "h" | "♥" | "♡" => Ok(Suit::Hearts),
+            "spades" | "s" | "♠" | "♤" => Ok(Suit::Spades),
+            _ => Err(format!("Invalid suit: {}", s)),
         }
     }
 }
@@ -36,7 +58,7 @@ impl Display for Suit {
             match self {
                 Suit::Clubs => "♣",
                 Suit::Diamonds => "♦",
-                Suit::Hearts => "♦",
+                Suit::Hearts => "♥",
                 Suit::Spades => "♠",
             }
         )